@@ -0,0 +1,62 @@
+use super::*;
+
+impl RaylibBackend {
+    // None if the device couldn't start
+    pub fn new(thread: &RaylibThread) -> Option<RaylibBackend> {
+        let audio = RaylibAudio::init_audio_device();
+        if !audio.is_audio_device_ready() { return None; }
+
+        let sounds = SoundEffect::ALL.iter().map(|effect| {
+            let mut samples = effect.get_samples();
+            let wave = ffi::Wave {
+                sampleCount: samples.len() as u32, sampleRate: SAMPLE_RATE, sampleSize: 16, channels: 1,
+                data: samples.as_mut_ptr() as *mut std::os::raw::c_void,
+            };
+            // The sound keeps its own copy of the samples
+            return unsafe { Sound::from_raw(ffi::LoadSoundFromWave(wave)) };
+        }).collect();
+
+        unsafe { ffi::SetAudioStreamBufferSizeDefault(MUSIC_CHUNK as i32); }
+        let stream = AudioStream::init_audio_stream(thread, SAMPLE_RATE, 16, 1);
+
+        return Some(RaylibBackend { sounds, stream, synth: MusicSynth::new(), is_music_playing: false, audio });
+    }
+}
+
+impl AudioBackend for RaylibBackend {
+    fn play_sound(self: &mut Self, effect: SoundEffect, volume: f32) {
+        let index = SoundEffect::ALL.iter().position(|e| *e == effect).unwrap_or(0);
+        let sound = &self.sounds[index];
+        self.audio.set_sound_volume(sound, volume);
+        self.audio.play_sound_multi(sound);
+    }
+
+    // Starts over from the first bar each time it comes back
+    fn update_music(self: &mut Self, level: Option<i32>, volume: f32) {
+        let Some(level) = level else {
+            if self.is_music_playing { self.audio.stop_audio_stream(&mut self.stream); }
+            self.is_music_playing = false;
+            return;
+        };
+
+        if !self.is_music_playing {
+            self.synth = MusicSynth::new();
+            self.audio.play_audio_stream(&mut self.stream);
+            self.is_music_playing = true;
+        }
+
+        self.synth.level = level;
+        self.audio.set_audio_stream_volume(&mut self.stream, volume);
+        while self.audio.is_audio_stream_processed(&self.stream) {
+            let mut samples = [0; MUSIC_CHUNK];
+            self.synth.fill(&mut samples);
+            // raylib-rs passes the size in bytes instead of samples, so this goes straight to raylib
+            unsafe { ffi::UpdateAudioStream(*self.stream, samples.as_ptr() as *const std::os::raw::c_void, MUSIC_CHUNK as i32); }
+        }
+    }
+}
+
+impl AudioBackend for NullBackend {
+    fn play_sound(self: &mut Self, _effect: SoundEffect, _volume: f32) {}
+    fn update_music(self: &mut Self, _level: Option<i32>, _volume: f32) {}
+}
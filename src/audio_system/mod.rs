@@ -0,0 +1,53 @@
+mod player;
+mod backends;
+mod synth;
+
+use std::cell::RefCell;
+use raylib::prelude::*;
+
+use crate::settings_system::AudioSettings;
+
+const SAMPLE_RATE: u32 = 22050;
+const MUSIC_CHUNK: usize = 2048; // Samples written to the music stream at a time
+const MAX_QUEUED: usize = 16;
+
+// Played at the end of the frame, so anything can ask for a sound without a handle to the audio.
+// Everything that plays sounds runs on the game's thread, and tests running at once don't share it
+thread_local! {
+    static QUEUE: RefCell<Vec<SoundEffect>> = const { RefCell::new(Vec::new()) };
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SoundEffect { PaddleBounce, EdgeBounce, LifeLost, TierUp, MenuMove, MenuConfirm }
+
+// Where sounds end up. Music is given the tier it should match, or None to stop it
+pub trait AudioBackend {
+    fn play_sound(self: &mut Self, effect: SoundEffect, volume: f32);
+    fn update_music(self: &mut Self, level: Option<i32>, volume: f32);
+}
+
+// Plays everything through raylib's audio device
+pub struct RaylibBackend {
+    sounds: Vec<Sound>,     // In the order of SoundEffect::ALL
+    stream: AudioStream,
+    synth: MusicSynth,
+    is_music_playing: bool,
+    audio: RaylibAudio,     // Last, so the device closes after everything on it is unloaded
+}
+
+// For machines without an audio device, like headless CI
+pub struct NullBackend;
+
+pub struct AudioPlayer {
+    backend: Box<dyn AudioBackend>,
+    settings: AudioSettings,
+    revision: u32,  // Of the settings last read
+}
+
+// Generates the music, which gets more layers with each tier
+pub struct MusicSynth {
+    pub level: i32,
+    step: u32,          // 16th notes since it started
+    step_time: f32,     // Seconds into the current step
+    noise: u32,
+}
@@ -0,0 +1,90 @@
+use super::*;
+use crate::settings_system::Settings;
+
+impl AudioPlayer {
+    // Falls back to silence if there's no audio device
+    pub fn new(thread: &RaylibThread) -> AudioPlayer {
+        let settings = Settings::load().audio;
+        match RaylibBackend::new(thread) {
+            Some(backend) => return Self::with_backend(Box::new(backend), settings),
+            None => {
+                println!("No audio device found, playing without sound...");
+                return Self::with_backend(Box::new(NullBackend), settings);
+            }
+        }
+    }
+
+    pub fn with_backend(backend: Box<dyn AudioBackend>, settings: AudioSettings) -> AudioPlayer {
+        return AudioPlayer { backend, settings, revision: Settings::get_revision() };
+    }
+
+    // Queued until the next update, extra sounds are dropped if too many pile up
+    pub fn play(effect: SoundEffect) {
+        QUEUE.with_borrow_mut(|queue| if queue.len() < MAX_QUEUED { queue.push(effect); });
+    }
+
+    pub fn update(self: &mut Self, music_level: Option<i32>) {
+        if Settings::get_revision() != self.revision {
+            self.revision = Settings::get_revision();
+            self.settings = Settings::load().audio;
+        }
+
+        // The same sound twice in a frame would just be louder
+        let mut effects: Vec<SoundEffect> = Vec::new();
+        for effect in QUEUE.with_borrow_mut(std::mem::take) {
+            if !effects.contains(&effect) { effects.push(effect); }
+        }
+
+        let master = self.settings.master_volume;
+        for effect in effects { self.backend.play_sound(effect, master * self.settings.effects_volume); }
+        self.backend.update_music(music_level, master * self.settings.music_volume);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use super::*;
+
+    // Keeps everything it's asked to play, shared with the test after the player takes it
+    #[derive(Default)]
+    struct Recording {
+        sounds: Vec<(SoundEffect, f32)>,
+        music: Vec<(Option<i32>, f32)>,
+    }
+
+    struct RecordingBackend(Rc<RefCell<Recording>>);
+
+    impl AudioBackend for RecordingBackend {
+        fn play_sound(self: &mut Self, effect: SoundEffect, volume: f32) { self.0.borrow_mut().sounds.push((effect, volume)); }
+        fn update_music(self: &mut Self, level: Option<i32>, volume: f32) { self.0.borrow_mut().music.push((level, volume)); }
+    }
+
+    fn new_player() -> (AudioPlayer, Rc<RefCell<Recording>>) {
+        let recording = Rc::new(RefCell::new(Recording::default()));
+        let settings = AudioSettings { master_volume: 0.5, music_volume: 0.5, effects_volume: 0.8 };
+        return (AudioPlayer::with_backend(Box::new(RecordingBackend(recording.clone())), settings), recording);
+    }
+
+    #[test]
+    fn sounds_play_once_per_frame_in_order() {
+        let (mut player, recording) = new_player();
+        for effect in [SoundEffect::EdgeBounce, SoundEffect::PaddleBounce, SoundEffect::EdgeBounce] { AudioPlayer::play(effect); }
+        player.update(Some(2));
+        player.update(None);
+
+        let recording = recording.borrow();
+        assert!(recording.sounds == vec![(SoundEffect::EdgeBounce, 0.4), (SoundEffect::PaddleBounce, 0.4)]);
+        assert!(recording.music == vec![(Some(2), 0.25), (None, 0.25)]);
+    }
+
+    #[test]
+    fn sounds_past_the_limit_are_dropped() {
+        let (mut player, recording) = new_player();
+        for _ in 0..MAX_QUEUED { AudioPlayer::play(SoundEffect::MenuMove); }
+        AudioPlayer::play(SoundEffect::TierUp);
+        player.update(None);
+
+        assert!(recording.borrow().sounds == vec![(SoundEffect::MenuMove, 0.4)]);
+    }
+}
@@ -0,0 +1,136 @@
+use std::f32::consts::TAU;
+
+use super::*;
+
+// Semitones above A2 for each bar's chord: Am, F, C, G
+const CHORDS: [(i32, [i32; 3]); 4] = [(0, [0, 3, 7]), (-4, [0, 4, 7]), (3, [0, 4, 7]), (-2, [0, 4, 7])];
+const ROOT_HZ: f32 = 110.0;
+const LEAD_NOTES: [i32; 8] = [12, 15, 19, 17, 15, 12, 10, 7]; // One per beat, over two bars
+
+fn get_frequency(semitones: i32) -> f32 {
+    return ROOT_HZ * 2.0_f32.powf(semitones as f32 / 12.0);
+}
+
+fn square(phase: f32) -> f32 {
+    return if phase.fract() < 0.5 { 1.0 } else { -1.0 };
+}
+
+// A tone that slides between two frequencies, fading out towards the end
+fn sweep(from_hz: f32, to_hz: f32, duration: f32, is_square: bool) -> Vec<f32> {
+    let length = (duration * SAMPLE_RATE as f32) as usize;
+    let mut phase = 0.0;
+
+    return (0..length).map(|i| {
+        let progress = i as f32 / length as f32;
+        phase += (from_hz + (to_hz - from_hz) * progress) / SAMPLE_RATE as f32;
+        let wave = if is_square { square(phase) * 0.5 } else { (phase * TAU).sin() };
+        return wave * (1.0 - progress);
+    }).collect();
+}
+
+impl SoundEffect {
+    pub const ALL: [SoundEffect; 6] = [
+        SoundEffect::PaddleBounce, SoundEffect::EdgeBounce, SoundEffect::LifeLost,
+        SoundEffect::TierUp, SoundEffect::MenuMove, SoundEffect::MenuConfirm
+    ];
+
+    // 16-bit mono at SAMPLE_RATE
+    pub fn get_samples(self: &Self) -> Vec<i16> {
+        let samples = match self {
+            SoundEffect::PaddleBounce => sweep(520.0, 520.0, 0.07, true),
+            SoundEffect::EdgeBounce => sweep(300.0, 300.0, 0.05, true).iter().map(|s| s * 0.6).collect(),
+            SoundEffect::LifeLost => sweep(440.0, 110.0, 0.45, true),
+            SoundEffect::TierUp => [523.0, 659.0, 784.0, 1047.0].iter().flat_map(|hz| sweep(*hz, *hz, 0.09, false)).collect(),
+            SoundEffect::MenuMove => sweep(700.0, 700.0, 0.03, false),
+            SoundEffect::MenuConfirm => sweep(880.0, 1320.0, 0.08, false),
+        };
+        return samples.iter().map(|s| (s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16).collect();
+    }
+}
+
+impl MusicSynth {
+    pub fn new() -> MusicSynth {
+        return MusicSynth { level: 0, step: 0, step_time: 0.0, noise: 1 };
+    }
+
+    // Faster with each tier, and a new layer for each of the first few
+    pub fn fill(self: &mut Self, samples: &mut [i16]) {
+        let step_length = 60.0 / (100.0 + self.level.min(6) as f32 * 10.0) / 4.0;
+
+        for sample in samples.iter_mut() {
+            self.step_time += 1.0 / SAMPLE_RATE as f32;
+            if self.step_time >= step_length {
+                self.step_time -= step_length;
+                self.step += 1;
+            }
+            *sample = (self.get_sample(step_length).clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+        }
+    }
+
+    fn get_sample(self: &mut Self, step_length: f32) -> f32 {
+        let t = self.step_time;
+        let beat_step = self.step % 4;
+        let (root, chord) = CHORDS[(self.step / 16 % 4) as usize];
+        let mut value = 0.0;
+
+        // Bass on every 8th note
+        if self.step.is_multiple_of(2) {
+            value += square(get_frequency(root) * t) * 0.25 * (-t * 6.0).exp();
+        }
+
+        // Hi-hat off the beat
+        if self.level >= 1 && beat_step == 2 {
+            self.noise = self.noise.wrapping_mul(1664525).wrapping_add(1013904223);
+            let noise = (self.noise >> 16) as f32 / 32768.0 - 1.0;
+            value += noise * 0.12 * (-t * 40.0).exp();
+        }
+
+        // Arpeggio over the chord, an octave up
+        if self.level >= 2 {
+            let note = root + 12 + chord[(self.step % 3) as usize];
+            value += (get_frequency(note) * t * TAU).sin() * 0.18 * (-t * 10.0).exp();
+        }
+
+        // Kick on the beat, dropping in pitch
+        if self.level >= 3 && beat_step == 0 {
+            value += ((150.0 - t * 600.0).max(50.0) * t * TAU).sin() * 0.4 * (-t * 12.0).exp();
+        }
+
+        // Lead holding a note for the whole beat
+        if self.level >= 4 {
+            let note = root + LEAD_NOTES[(self.step / 4 % 8) as usize];
+            let beat_time = t + beat_step as f32 * step_length;
+            value += square(get_frequency(note) * 2.0 * t) * 0.08 * (-beat_time * 2.0).exp();
+        }
+        return value;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_energy(level: i32) -> f32 {
+        let mut synth = MusicSynth::new();
+        synth.level = level;
+        let mut samples = [0; SAMPLE_RATE as usize];
+        synth.fill(&mut samples);
+        return samples.iter().map(|s| (*s as f32).abs()).sum();
+    }
+
+    #[test]
+    fn effects_are_short_and_fade_out() {
+        for effect in SoundEffect::ALL {
+            let samples = effect.get_samples();
+            assert!(!samples.is_empty() && samples.len() < SAMPLE_RATE as usize);
+            assert!(samples.iter().any(|s| s.abs() > 1000));
+            assert!(samples.last().unwrap().abs() < 2000);
+        }
+    }
+
+    #[test]
+    fn higher_tiers_add_layers() {
+        let energies: Vec<f32> = (0..5).map(get_energy).collect();
+        assert!(energies.windows(2).all(|pair| pair[1] > pair[0]));
+    }
+}
@@ -0,0 +1,214 @@
+use super::*;
+
+impl Hit {
+    // Where a circle ends up if it bounces off with the rest of its path
+    pub fn reflect(self: &Self, end: Vector2) -> Vector2 {
+        let remaining = end - self.position;
+        return self.position + remaining - self.normal * 2.0 * remaining.dot(self.normal);
+    }
+}
+
+impl Collider {
+    // Sweeps a circle from start to end, so fast circles can't pass through thin colliders between frames.
+    // Circles moving away from a collider they overlap aren't stopped, so they can leave it
+    pub fn sweep_circle(self: &Self, start: Vector2, end: Vector2, radius: f32) -> Option<Hit> {
+        let path = end - start;
+        if let Some(hit) = self.get_overlap(start, radius) {
+            return if path.dot(hit.normal) < 0.0 { Some(hit) } else { None };
+        }
+
+        match *self {
+            Collider::Wall(point, normal) => {
+                // Distances of the circle's edge to the wall, negative once past it
+                let start_distance = (start - point).dot(normal) - radius;
+                let end_distance = (end - point).dot(normal) - radius;
+                if end_distance >= 0.0 { return None; }
+
+                let time = start_distance / (start_distance - end_distance);
+                return Some(Hit { time, position: start + path * time, normal });
+            },
+            Collider::Rect(rect) => return Self::sweep_circle_rect(rect, start, path, radius),
+            Collider::Circle(center, circle_radius) => {
+                let time = Self::get_circle_time(start, path, center, radius + circle_radius)?;
+                let position = start + path * time;
+                return Some(Hit { time, position, normal: (position - center).normalized() });
+            },
+        }
+    }
+
+    // How to push an overlapping circle out the closest way, if it overlaps
+    pub fn get_overlap(self: &Self, center: Vector2, radius: f32) -> Option<Hit> {
+        match *self {
+            Collider::Wall(point, normal) => {
+                let depth = radius - (center - point).dot(normal);
+                if depth <= 0.0 { return None; }
+                return Some(Hit { time: 0.0, position: center + normal * depth, normal });
+            },
+            Collider::Rect(rect) => return Self::get_rect_overlap(rect, center, radius),
+            Collider::Circle(circle_center, circle_radius) => {
+                let distance = center.distance_to(circle_center);
+                if distance >= radius + circle_radius { return None; }
+
+                // Exactly on the center there's no closest way out, so it's pushed up
+                let normal = if distance > 0.0 { (center - circle_center) / distance } else { Vector2::new(0.0, -1.0) };
+                return Some(Hit { time: 0.0, position: circle_center + normal * (radius + circle_radius), normal });
+            },
+        }
+    }
+
+    fn sweep_circle_rect(rect: Rectangle, start: Vector2, path: Vector2, radius: f32) -> Option<Hit> {
+        // The circle touches the rect when its center enters the rect grown by the radius
+        let min = Vector2::new(rect.x - radius, rect.y - radius);
+        let max = Vector2::new(rect.x + rect.width + radius, rect.y + rect.height + radius);
+        let (enter_x, exit_x) = Self::get_slab_times(start.x, path.x, min.x, max.x)?;
+        let (enter_y, exit_y) = Self::get_slab_times(start.y, path.y, min.y, max.y)?;
+
+        let enter = enter_x.max(enter_y);
+        if enter > exit_x.min(exit_y) || !(0.0..=1.0).contains(&enter) { return None; }
+
+        // Entering along a face, the grown rect is exact
+        let position = start + path * enter;
+        let on_x_face = position.y >= rect.y && position.y <= rect.y + rect.height;
+        let on_y_face = position.x >= rect.x && position.x <= rect.x + rect.width;
+        if on_x_face || on_y_face {
+            let normal = if enter_x > enter_y { Vector2::new(-path.x.signum(), 0.0) } 
+                         else { Vector2::new(0.0, -path.y.signum()) };
+            return Some(Hit { time: enter, position, normal });
+        }
+
+        // Around the corners it's rounded, so it's a circle around the closest one instead
+        let corner = Vector2::new(position.x.clamp(rect.x, rect.x + rect.width), position.y.clamp(rect.y, rect.y + rect.height));
+        let time = Self::get_circle_time(start, path, corner, radius)?;
+        let position = start + path * time;
+        return Some(Hit { time, position, normal: (position - corner).normalized() });
+    }
+
+    fn get_rect_overlap(rect: Rectangle, center: Vector2, radius: f32) -> Option<Hit> {
+        let closest = Vector2::new(center.x.clamp(rect.x, rect.x + rect.width), center.y.clamp(rect.y, rect.y + rect.height));
+        let distance = center.distance_to(closest);
+        if distance >= radius { return None; }
+
+        if distance > 0.0 {
+            let normal = (center - closest) / distance;
+            return Some(Hit { time: 0.0, position: closest + normal * radius, normal });
+        }
+
+        // Center inside the rect, so it leaves through the nearest side
+        let sides = [
+            (center.x - rect.x, Vector2::new(-1.0, 0.0)), (rect.x + rect.width - center.x, Vector2::new(1.0, 0.0)),
+            (center.y - rect.y, Vector2::new(0.0, -1.0)), (rect.y + rect.height - center.y, Vector2::new(0.0, 1.0)),
+        ];
+        let (depth, normal) = sides.into_iter().min_by(|a, b| a.0.total_cmp(&b.0))?;
+        return Some(Hit { time: 0.0, position: center + normal * (depth + radius), normal });
+    }
+
+    // Times a ray enters and leaves the space between min and max on one axis
+    fn get_slab_times(start: f32, path: f32, min: f32, max: f32) -> Option<(f32, f32)> {
+        if path == 0.0 {
+            return if start >= min && start <= max { Some((f32::NEG_INFINITY, f32::INFINITY)) } else { None };
+        }
+
+        let (a, b) = ((min - start) / path, (max - start) / path);
+        return Some((a.min(b), a.max(b)));
+    }
+
+    // First time a ray gets within radius of a point, if it does along its path
+    fn get_circle_time(start: Vector2, path: Vector2, center: Vector2, radius: f32) -> Option<f32> {
+        let offset = start - center;
+        let a = path.dot(path);
+        let b = 2.0 * offset.dot(path);
+        let c = offset.dot(offset) - radius * radius;
+
+        let discriminant = b * b - 4.0 * a * c;
+        if a == 0.0 || discriminant < 0.0 { return None; }
+
+        let time = (-b - discriminant.sqrt()) / (2.0 * a);
+        return if (0.0..=1.0).contains(&time) { Some(time) } else { None };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Thin and tall, like a paddle
+    fn paddle() -> Collider {
+        return Collider::Rect(Rectangle::new(100.0, 100.0, 10.0, 60.0));
+    }
+
+    #[test]
+    fn fast_circle_doesnt_tunnel_through() {
+        let hit = paddle().sweep_circle(Vector2::new(300.0, 130.0), Vector2::new(-300.0, 130.0), 5.0).unwrap();
+
+        assert_eq!(hit.normal, Vector2::new(1.0, 0.0));
+        assert_eq!(hit.position, Vector2::new(115.0, 130.0));
+        assert!((hit.time - 185.0 / 600.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn caps_push_up_and_down() {
+        let top = paddle().sweep_circle(Vector2::new(105.0, 50.0), Vector2::new(105.0, 130.0), 5.0).unwrap();
+        let bottom = paddle().sweep_circle(Vector2::new(105.0, 200.0), Vector2::new(105.0, 130.0), 5.0).unwrap();
+
+        assert_eq!(top.normal, Vector2::new(0.0, -1.0));
+        assert_eq!(top.position.y, 95.0);
+        assert_eq!(bottom.normal, Vector2::new(0.0, 1.0));
+        assert_eq!(bottom.position.y, 165.0);
+    }
+
+    #[test]
+    fn corners_are_rounded() {
+        // Crosses the grown rect's corner, but passes outside the rounded one
+        assert!(paddle().sweep_circle(Vector2::new(85.0, 106.0), Vector2::new(106.0, 85.0), 5.0).is_none());
+
+        let hit = paddle().sweep_circle(Vector2::new(90.0, 90.0), Vector2::new(100.0, 100.0), 5.0).unwrap();
+        let diagonal = Vector2::new(-1.0, -1.0).normalized();
+        assert!((hit.normal - diagonal).length() < 1e-5);
+    }
+
+    #[test]
+    fn misses_and_short_paths_dont_hit() {
+        assert!(paddle().sweep_circle(Vector2::new(300.0, 0.0), Vector2::new(-300.0, 0.0), 5.0).is_none());
+        assert!(paddle().sweep_circle(Vector2::new(300.0, 130.0), Vector2::new(200.0, 130.0), 5.0).is_none());
+    }
+
+    #[test]
+    fn overlaps_only_stop_circles_moving_in() {
+        let moving_in = paddle().sweep_circle(Vector2::new(112.0, 130.0), Vector2::new(100.0, 130.0), 5.0).unwrap();
+        assert_eq!(moving_in.time, 0.0);
+        assert_eq!(moving_in.position, Vector2::new(115.0, 130.0));
+
+        assert!(paddle().sweep_circle(Vector2::new(112.0, 130.0), Vector2::new(130.0, 130.0), 5.0).is_none());
+    }
+
+    #[test]
+    fn walls_reflect_by_the_penetration() {
+        let floor = Collider::Wall(Vector2::new(0.0, 100.0), Vector2::new(0.0, -1.0));
+        let end = Vector2::new(40.0, 115.0);
+        let hit = floor.sweep_circle(Vector2::new(0.0, 55.0), end, 5.0).unwrap();
+
+        assert!((hit.time - 2.0 / 3.0).abs() < 1e-5);
+        assert_eq!(hit.position.y, 95.0);
+        assert_eq!(hit.normal, Vector2::new(0.0, -1.0));
+        assert!((hit.reflect(end) - Vector2::new(40.0, 75.0)).length() < 1e-4);
+    }
+
+    #[test]
+    fn walls_push_out_circles_past_them() {
+        let floor = Collider::Wall(Vector2::new(0.0, 100.0), Vector2::new(0.0, -1.0));
+
+        let hit = floor.sweep_circle(Vector2::new(0.0, 98.0), Vector2::new(0.0, 99.0), 5.0).unwrap();
+        assert_eq!(hit.position, Vector2::new(0.0, 95.0));
+        assert!(floor.sweep_circle(Vector2::new(0.0, 98.0), Vector2::new(0.0, 90.0), 5.0).is_none());
+    }
+
+    #[test]
+    fn circles_bounce_off_their_center() {
+        let bumper = Collider::Circle(Vector2::new(100.0, 100.0), 20.0);
+        let hit = bumper.sweep_circle(Vector2::new(0.0, 100.0), Vector2::new(100.0, 100.0), 5.0).unwrap();
+
+        assert_eq!(hit.position, Vector2::new(75.0, 100.0));
+        assert_eq!(hit.normal, Vector2::new(-1.0, 0.0));
+        assert!(bumper.sweep_circle(Vector2::new(0.0, 50.0), Vector2::new(200.0, 50.0), 5.0).is_none());
+    }
+}
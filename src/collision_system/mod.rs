@@ -0,0 +1,19 @@
+mod collider;
+
+use raylib::prelude::*;
+
+// Something a moving circle can bounce on
+#[derive(Clone, Copy)]
+pub enum Collider {
+    Wall(Vector2, Vector2),     // Endless wall through the point, solid behind its normal
+    Rect(Rectangle),
+    Circle(Vector2, f32),       // Center and radius
+}
+
+// Where a moving circle first touches a collider along its path
+#[derive(Clone, Copy)]
+pub struct Hit {
+    pub time: f32,          // Fraction of the path travelled before touching, from 0 to 1
+    pub position: Vector2,  // Center of the circle when touching
+    pub normal: Vector2,    // Points away from the surface that was hit
+}
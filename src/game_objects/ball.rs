@@ -1,13 +1,12 @@
 use raylib::prelude::*;
 
-use crate::utils::*;
 use crate::game_objects::*;
 
 impl GameObject for Ball{
-    fn update(&mut self, rl: &RaylibHandle, input: &InputData) {
+    fn update(&mut self, dt: f32, input: &InputData) {
         self.update_velocity(input);
-        self.update_color(rl, input);
-        self.translate(rl);
+        self.update_color(dt, input);
+        self.translate(dt);
     }
 }
 
@@ -24,17 +23,17 @@ impl Ball {
     }
 
     // Fluctuates between grey and white
-    fn update_color(&mut self, rl: &RaylibHandle, input: &InputData) {
+    fn update_color(&mut self, dt: f32, input: &InputData) {
         let mut alpha = self.color.a as f32;
         let input_intensity = (input.dir.x.abs() + input.dir.y.abs()) / 2.0;
 
         // go closer to white if receiving self.input
         if input.raw_dir != Vector2::zero() { 
-            alpha += 680.0 * input_intensity * 10.0 * rl.get_frame_time(); // TODO: Use logarithmic interpolation instead of a linear one
+            alpha += 680.0 * input_intensity * 10.0 * dt; // TODO: Use logarithmic interpolation instead of a linear one
         }
         // go closer to grey if not
         else { 
-            alpha -= 500.0 * (1.0 - input_intensity).powf(2.0) * rl.get_frame_time(); 
+            alpha -= 500.0 * (1.0 - input_intensity).powf(2.0) * dt; 
         }
         
         self.color = self.colors[self.lives as usize - 1];
@@ -42,10 +41,9 @@ impl Ball {
     }
 
     // Rust compiler don't let me name it move() >:(
-    fn translate(&mut self, rl: &RaylibHandle)
+    fn translate(&mut self, dt: f32)
     {
-        self.position += self.velocity * rl.get_frame_time();
-        self.position.y = self.position.y.clamp(self.radius, SCREEN_SIZE.y - self.radius); // keep it inside the screen
+        self.position += self.velocity * dt;
     }
 
     fn update_velocity(&mut self, input: &InputData) {
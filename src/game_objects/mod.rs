@@ -1,19 +1,22 @@
 mod ball;
 mod paddle;
+mod pickup;
 
 use raylib::prelude::*;
+use serde::{Serialize, Deserialize};
 use crate::input_system::*;
 
 pub const PADDLE_PADDING: f32 = 20.0;
 pub const MAX_PLAYER_SPEED: f32 = 500.0;
 pub const INITIAL_PADDLE_RANGE: f32 = 0.5;
 pub const INITIAL_PADDLE_SPEED: f32 = 500.0;
+pub const LIFE_RADIUS: f32 = 0.8; // The ball gets smaller with each life lost
 
 pub const PADDLE_SIZE: Vector2 = Vector2 { x: 11.0, y: 65.0 };
 
 // Common trait for GameObjects
 pub trait GameObject {
-    fn update(&mut self, rl: &RaylibHandle, input: &InputData);
+    fn update(&mut self, dt: f32, input: &InputData);
 }
 
 
@@ -46,4 +49,23 @@ pub struct Paddle {
     pub color: Color,
     pub colors: [Color; 2],
     pub player_controlled: bool,
-}
\ No newline at end of file
+}
+
+// Collected by the ball, some help and some get in the way
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PowerUp {
+    Shrink,             // Smaller ball
+    ExtraLife,          // Up to the lives the ball has colors for
+    PaddleSlow,
+    Shield,             // Losing the ball doesn't cost a life
+    ScoreMultiplier,
+    Ghost,              // The ball goes through the paddle it's heading to
+}
+
+// A power-up waiting on the field
+pub struct Pickup {
+    pub power_up: PowerUp,
+    pub position: Vector2,
+    pub radius: f32,
+    pub lifetime: f32,  // Seconds left before it's gone
+}
@@ -5,15 +5,15 @@ use crate::game_objects::*;
 
 
 impl GameObject for Paddle {
-    fn update(&mut self, rl: &RaylibHandle, input: &InputData) {        
+    fn update(&mut self, dt: f32, input: &InputData) {        
         self.update_velocity(input);
-        self.update_color(&rl);
-        self.translate(rl);
+        self.update_color(dt);
+        self.translate(dt);
     }
 }
 
 impl Paddle {
-    fn update_color(&mut self, rl: &RaylibHandle) {
+    fn update_color(&mut self, dt: f32) {
         if !self.player_controlled { 
             self.color = Color::GRAY;
             return; 
@@ -30,14 +30,14 @@ impl Paddle {
             let step = closeness.powf(3.0) * ((self.color.a as f32 - 130.0) / (255.0 - 130.0));
             self.color.g = lerp(self.colors[0].g as f32, self.colors[1].g as f32, step) as u8;
             self.color.b = lerp(self.colors[0].b as f32, self.colors[1].b as f32, step) as u8;
-            alpha += 50.0 * input_intensity * 10.0 * rl.get_frame_time();                      // TODO: Use logarithmic interpolation instead of linear
+            alpha += 50.0 * input_intensity * 10.0 * dt;                      // TODO: Use logarithmic interpolation instead of linear
         }
         // go closer to grey if not
         else {
             let step = (self.color.a as f32 - 130.0) / (255.0 - 130.0);
             self.color.g = lerp(self.colors[0].g as f32, self.colors[1].g as f32, step) as u8;
             self.color.b = lerp(self.colors[0].b as f32, self.colors[1].b as f32, step) as u8; 
-            alpha -= 500.0 * rl.get_frame_time();
+            alpha -= 500.0 * dt;
         }
         
         // self.color = self.colors[self.lives as usize - 1];
@@ -69,8 +69,8 @@ impl Paddle {
     }
 
     // Rust compiler don't let me name it move() >:(
-    fn translate(&mut self, rl: &RaylibHandle) {
-        self.position.y += self.velocity * rl.get_frame_time();
+    fn translate(&mut self, dt: f32) {
+        self.position.y += self.velocity * dt;
         self.position.y = self.position.y.clamp(10.0, SCREEN_SIZE.y - self.size.y - 10.0);
 
         self.hitbox.x = self.position.x;
@@ -78,7 +78,7 @@ impl Paddle {
     }
 
     // How close the player_pos is from the paddle, normalized
-    fn get_player_pos_closeness(&self) -> f32 {
+    pub fn get_player_pos_closeness(&self) -> f32 {
         let view_distance = SCREEN_SIZE.x * self.view_range;
         let distance = self.player_pos.x - (self.position.x);
         return 1.0 - (distance / view_distance * distance.signum()).clamp(0.0, 1.0);
@@ -0,0 +1,66 @@
+use raylib::prelude::*;
+
+use crate::game_objects::*;
+
+impl PowerUp {
+    pub const ALL: [PowerUp; 6] = [
+        PowerUp::Shrink, PowerUp::ExtraLife, PowerUp::PaddleSlow,
+        PowerUp::Shield, PowerUp::ScoreMultiplier, PowerUp::Ghost
+    ];
+
+    pub fn get_name(self: &Self) -> &'static str {
+        match self {
+            PowerUp::Shrink => "Shrink",
+            PowerUp::ExtraLife => "Extra Life",
+            PowerUp::PaddleSlow => "Paddle Slow",
+            PowerUp::Shield => "Shield",
+            PowerUp::ScoreMultiplier => "Score x2",
+            PowerUp::Ghost => "Ghost",
+        }
+    }
+
+    // Drawn on the pickup
+    pub fn get_letter(self: &Self) -> &'static str {
+        match self {
+            PowerUp::Shrink => "S",
+            PowerUp::ExtraLife => "+",
+            PowerUp::PaddleSlow => "P",
+            PowerUp::Shield => "D",
+            PowerUp::ScoreMultiplier => "2",
+            PowerUp::Ghost => "G",
+        }
+    }
+
+    pub fn get_color(self: &Self) -> Color {
+        match self {
+            PowerUp::Shrink => Color::SKYBLUE,
+            PowerUp::ExtraLife => Color::LIME,
+            PowerUp::PaddleSlow => Color::ORANGE,
+            PowerUp::Shield => Color::GOLD,
+            PowerUp::ScoreMultiplier => Color::VIOLET,
+            PowerUp::Ghost => Color::LIGHTGRAY,
+        }
+    }
+
+    // Seconds it lasts, instant ones don't last at all
+    pub fn get_duration(self: &Self) -> f32 {
+        match self {
+            PowerUp::Shrink => 8.0,
+            PowerUp::ExtraLife => 0.0,
+            PowerUp::PaddleSlow => 6.0,
+            PowerUp::Shield => 10.0,
+            PowerUp::ScoreMultiplier => 10.0,
+            PowerUp::Ghost => 4.0,
+        }
+    }
+}
+
+impl Pickup {
+    pub fn new(power_up: PowerUp, position: Vector2, radius: f32, lifetime: f32) -> Pickup {
+        return Pickup { power_up, position, radius, lifetime };
+    }
+
+    pub fn is_touching(self: &Self, ball: &Ball) -> bool {
+        return self.position.distance_to(ball.position) < self.radius + ball.radius;
+    }
+}
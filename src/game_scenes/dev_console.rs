@@ -0,0 +1,290 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use raylib::prelude::*;
+use raylib::consts::KeyboardKey::*;
+use regex::Regex;
+
+use crate::utils::*;
+use crate::game_objects::*;
+use crate::game_scenes::*;
+use crate::game_scenes::game_loop::DIFFICULTY_CHECKPOINTS;
+
+const PLACEHOLDER: &str = "TYPE HELP FOR COMMANDS";
+const LOG_LINES: usize = 6;
+const FIELD_FORMAT: &str = "[A-Z0-9 .-]"; // Only what GetKeyPressed gives without modifiers
+
+impl DevConsole {
+    pub fn print(self: &mut Self, line: &str) {
+        self.log.push(line.to_string());
+    }
+
+    // Returns the command submitted this frame, if any
+    pub fn update(self: &mut Self, rl: &RaylibHandle) -> Option<String> {
+        if rl.is_key_pressed(KEY_ESCAPE) {
+            self.is_open = false;
+            return None;
+        }
+
+        if rl.is_key_pressed(KEY_UP) { self.browse_history(-1); }
+        if rl.is_key_pressed(KEY_DOWN) { self.browse_history(1); }
+        self.field.update(rl);
+
+        let command = self.field.text.text.trim().to_string();
+        if !rl.is_key_pressed(KEY_ENTER) || command.is_empty() || command == PLACEHOLDER { return None; }
+
+        self.print(&format!("> {}", command));
+        self.history.retain(|c| *c != command);
+        self.history.push(command.clone());
+        self.history_index = None;
+        self.set_field_text(PLACEHOLDER);
+        return Some(command);
+    }
+
+    // Steps through the previous commands, going back to an empty field after the newest
+    fn browse_history(self: &mut Self, step: i32) {
+        if self.history.is_empty() { return; }
+
+        let newest = self.history.len() as i32 - 1;
+        let index = match self.history_index {
+            Some(i) => i as i32 + step,
+            None if step < 0 => newest,
+            None => return,
+        };
+
+        self.history_index = if index > newest { None } else { Some(index.max(0) as usize) };
+        let text = self.history_index.map_or(PLACEHOLDER.to_string(), |i| self.history[i].clone());
+        self.set_field_text(&text);
+    }
+
+    fn set_field_text(self: &mut Self, text: &str) {
+        self.field.text.text = text.to_string();
+        self.field.text.centralize();
+    }
+
+    pub fn get_elements(self: &Self, rl: &RaylibHandle) -> ScreenElements {
+        let first_line = self.log.len().saturating_sub(LOG_LINES);
+        let texts = self.log[first_line..].iter().enumerate().map(|(i, line)| {
+            let mut text = Text::new(line, Vector2::new(0.5, 0.6 + i as f32 * 0.045), Color::GREEN, 16);
+            text.pos.x = 10.0;
+            return text;
+        }).collect();
+
+        return ScreenElements::new(rl, texts, vec![], vec![self.field.clone()]);
+    }
+
+    pub fn new() -> DevConsole {
+        let mut field = TextField::new(Regex::new(FIELD_FORMAT).expect("Invalid regex"), PLACEHOLDER,
+                                       SCREEN_SIZE.x - 20.0, 18, Vector2::new(0.5, 0.9), 2.0,
+                                       vec![Color::GREEN, Color::new(20, 20, 20, 255)], 48);
+        field.is_selected = true;
+
+        return DevConsole {
+            is_open: false,
+            field,
+            log: Vec::new(),
+            history: Vec::new(),
+            history_index: None,
+        };
+    }
+}
+
+impl Tunable {
+    fn new(name: &'static str, get: fn(&GameLoop) -> f32, set: fn(&mut GameLoop, f32)) -> Tunable {
+        return Tunable { name, get, set, is_positive: false };
+    }
+
+    // For values that others are divided by
+    fn new_positive(name: &'static str, get: fn(&GameLoop) -> f32, set: fn(&mut GameLoop, f32)) -> Tunable {
+        return Tunable { name, get, set, is_positive: true };
+    }
+}
+
+impl Tunables {
+    pub fn new() -> Tunables {
+        return Tunables {
+            max_player_speed: MAX_PLAYER_SPEED,
+            paddle_range: INITIAL_PADDLE_RANGE,
+
+            edge_exponent: 1.65,
+            edge_min_angle: 0.4,
+            edge_max_angle: 0.55,
+
+            paddle_edge_zone: 73.0,
+            paddle_min_angle: 0.45,
+            paddle_max_angle: 1.0,
+            english_spin: 0.35,
+        };
+    }
+}
+
+impl GameLoop {
+    // Everything the console can set by name. Min and max pairs can't cross each other
+    fn get_tunables() -> Vec<Tunable> {
+        return vec![
+            Tunable::new("ball.speed", |g| g.ball.speed, |g, v| g.ball.speed = v),
+            Tunable::new("ball.radius", |g| g.ball.radius, |g, v| g.ball.radius = v),
+
+            // Speeds and ranges are scaled by the difficulty tier, so the current ones scale along
+            Tunable::new_positive("player.max-speed", |g| g.tunables.max_player_speed, |g, v| {
+                g.ball.speed *= v / g.tunables.max_player_speed;
+                g.tunables.max_player_speed = v;
+            }),
+            Tunable::new_positive("paddle.range", |g| g.tunables.paddle_range, |g, v| {
+                for paddle in [&mut g.left_paddle, &mut g.right_paddle] { paddle.view_range *= v / g.tunables.paddle_range; }
+                g.tunables.paddle_range = v;
+            }),
+            Tunable::new("paddle.speed", |g| g.paddle_speed, |g, v| {
+                g.paddle_speed = v;
+                g.update_paddle_speed();
+            }),
+            Tunable::new("paddle.width", |g| g.left_paddle.size.x, |g, v| {
+                for paddle in [&mut g.left_paddle, &mut g.right_paddle] { paddle.size.x = v; paddle.hitbox.width = v; }
+            }),
+            Tunable::new("paddle.height", |g| g.left_paddle.size.y, |g, v| {
+                for paddle in [&mut g.left_paddle, &mut g.right_paddle] { paddle.size.y = v; paddle.hitbox.height = v; }
+            }),
+
+            Tunable::new("edge.exponent", |g| g.tunables.edge_exponent, |g, v| g.tunables.edge_exponent = v),
+            Tunable::new("edge.min", |g| g.tunables.edge_min_angle, |g, v| g.tunables.edge_min_angle = v.min(g.tunables.edge_max_angle)),
+            Tunable::new("edge.max", |g| g.tunables.edge_max_angle, |g, v| g.tunables.edge_max_angle = v.max(g.tunables.edge_min_angle)),
+
+            Tunable::new("bounce.edge-zone", |g| g.tunables.paddle_edge_zone, |g, v| g.tunables.paddle_edge_zone = v),
+            Tunable::new("bounce.min", |g| g.tunables.paddle_min_angle, |g, v| g.tunables.paddle_min_angle = v.min(g.tunables.paddle_max_angle)),
+            Tunable::new("bounce.max", |g| g.tunables.paddle_max_angle, |g, v| g.tunables.paddle_max_angle = v.max(g.tunables.paddle_min_angle)),
+            Tunable::new("english.spin", |g| g.tunables.english_spin, |g, v| g.tunables.english_spin = v),
+        ];
+    }
+
+    // Runs a console command, returning what to print back
+    pub fn run_command(self: &mut Self, command: &str) -> String {
+        let command = command.to_lowercase();
+        let words: Vec<&str> = command.split_whitespace().collect();
+        let tunables = Self::get_tunables();
+        let find = |name: &str| tunables.iter().find(|t| t.name == name);
+
+        match words.as_slice() {
+            ["help"] => return "set <name> <value>, get <name>, list, tier <score>, lives <1-3>, spawn, seed <number>".to_string(),
+            ["list"] => return tunables.iter().map(|t| t.name).collect::<Vec<&str>>().join(", "),
+
+            ["get", name] => match find(name) {
+                Some(tunable) => return format!("{} = {}", name, (tunable.get)(self)),
+                None => return format!("Unknown value '{}', type list to see them", name),
+            },
+            ["set", name, value] => match (find(name), value.parse::<f32>()) {
+                (_, Ok(value)) if !value.is_finite() => return format!("'{}' isn't a number", value),
+                (Some(tunable), Ok(value)) if tunable.is_positive && value <= 0.0 => return format!("{} has to be above 0", name),
+                (Some(tunable), Ok(value)) => {
+                    (tunable.set)(self, value);
+                    return format!("{} = {}", name, (tunable.get)(self));
+                },
+                (None, _) => return format!("Unknown value '{}', type list to see them", name),
+                (_, Err(_)) => return format!("'{}' isn't a number", value),
+            },
+
+            // Goes through every checkpoint on the way, so the difficulty matches the score
+            ["tier", score] => match score.parse::<i32>() {
+                Ok(score) => {
+                    for checkpoint in DIFFICULTY_CHECKPOINTS.iter().filter(|c| **c <= score) {
+                        self.score = *checkpoint;
+                        self.update_difficulty();
+                    }
+                    self.score = score.max(0);
+                    return format!("Score {}, tier {}", self.score, self.tier);
+                },
+                Err(_) => return format!("'{}' isn't a score", score),
+            },
+            // The ball grows and shrinks with its lives, like when it loses or picks one up
+            ["lives", lives] => match lives.parse::<i32>() {
+                Ok(lives) => {
+                    let lives = lives.clamp(1, 3);
+                    self.ball.radius += (lives - self.ball.lives) as f32 * self.get_life_radius();
+                    self.ball.lives = lives;
+                    return format!("{} lives", self.ball.lives);
+                },
+                Err(_) => return format!("'{}' isn't a number", lives),
+            },
+            ["spawn"] => {
+                self.spawn_ball();
+                return "Ball respawned".to_string();
+            },
+            ["seed", seed] => match seed.parse::<u64>() {
+                Ok(seed) => {
+                    self.rng = StdRng::seed_from_u64(seed);
+                    return format!("Seed {}", seed);
+                },
+                Err(_) => return format!("'{}' isn't a seed", seed),
+            },
+            _ => return format!("Unknown command '{}', type help for a list", command),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_changes_registered_values() {
+        let mut game = GameLoop::new_scripted(GameMode::Singleplayer, GameRules::new());
+        game.run_command("SET BALL.SPEED 420");
+        game.run_command("set paddle.height 100");
+
+        assert_eq!(game.ball.speed, 420.0);
+        assert_eq!(game.right_paddle.hitbox.height, 100.0);
+    }
+
+    #[test]
+    fn min_and_max_values_dont_cross() {
+        let mut game = GameLoop::new_scripted(GameMode::Singleplayer, GameRules::new());
+        game.run_command("set edge.min 0.9");
+        assert_eq!(game.tunables.edge_min_angle, game.tunables.edge_max_angle);
+    }
+
+    #[test]
+    fn tier_applies_the_checkpoints_below_it() {
+        let mut game = GameLoop::new_scripted(GameMode::Singleplayer, GameRules::new());
+        game.run_command("tier 60");
+
+        assert_eq!(game.score, 60);
+        assert_eq!(game.tier, 3);
+        assert_eq!(game.checkpoint, 50);
+    }
+
+    #[test]
+    fn scaling_values_must_be_positive() {
+        let mut game = GameLoop::new_scripted(GameMode::Singleplayer, GameRules::new());
+        let speed = game.ball.speed;
+
+        assert!(game.run_command("set player.max-speed 0").ends_with("has to be above 0"));
+        assert!(game.run_command("set paddle.range nan").ends_with("isn't a number"));
+        game.run_command("set player.max-speed 100");
+        assert!(game.ball.speed.is_finite() && game.ball.speed != speed);
+    }
+
+    #[test]
+    fn lives_resize_the_ball() {
+        let mut game = GameLoop::new_scripted(GameMode::Singleplayer, GameRules::new());
+        game.run_command("lives 3");
+        let radius = game.ball.radius;
+
+        game.run_command("lives 1");
+        assert_eq!(game.ball.radius, radius - LIFE_RADIUS * 2.0);
+    }
+
+    #[test]
+    fn unknown_commands_are_reported() {
+        let mut game = GameLoop::new_scripted(GameMode::Singleplayer, GameRules::new());
+        assert!(game.run_command("jump").starts_with("Unknown command"));
+        assert!(game.run_command("set ball.colour 1").starts_with("Unknown value"));
+    }
+
+    #[test]
+    fn every_tunable_can_be_typed() {
+        let format = Regex::new(FIELD_FORMAT).expect("Invalid regex");
+        let command = |name: &str| format!("SET {} 1", name.to_uppercase());
+
+        for tunable in GameLoop::get_tunables() {
+            assert!(command(tunable.name).chars().all(|c| format.is_match(&c.to_string())), "Can't type '{}'", tunable.name);
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use rand::*;
+use rand::rngs::StdRng;
 use raylib::prelude::*;
 use raylib::ffi::KeyboardKey::*;
 
@@ -6,21 +7,150 @@ use crate::input_system::*;
 use crate::utils::*;
 use crate::game_scenes::*;
 use crate::game_objects::*;
+use crate::collision_system::*;
+use crate::settings_system::{Settings, InputSettings};
+use crate::audio_system::{AudioPlayer, SoundEffect};
+use crate::game_scenes::rules::get_english_angle;
+use crate::game_scenes::power_ups::PICKUP_INTERVAL;
+
+const PADDLE_REACH: f32 = 5.0; // Paddles catch the ball a bit before it touches them
+const MAX_BOUNCES_PER_FRAME: usize = 4;
+const KICK_DECAY: f32 = 1.5; // Bumper kicks wear off per second
+pub const DIFFICULTY_CHECKPOINTS: [i32; 5] = [0, 10, 25, 50, 75]; // Score each tier starts at
 
 impl GameScene for GameLoop {
     fn update(self: &mut Self, rl: &RaylibHandle){
-        let ball_input = self.players_input[0].get_data(rl);
-        let paddle_input = self.players_input[1].get_data(rl);
+        // The last frame was only shown once its drawing ended
+        self.update_input_lag(rl.get_time());
+        self.update_devices();
+
+        // Freeze everything while typing in the console
+        if self.console.is_open {
+            if let Some(command) = self.console.update(rl) {
+                let output = self.run_command(&command);
+                self.console.print(&output);
+            }
+            return;
+        }
+
+        // Toggle pause, skipping the menu this frame so it doesn't read the same press
+        let pause_pressed = self.players_input.iter_mut().any(|p| p.is_action_pressed(InputAction::Pause));
+        if pause_pressed && (!self.is_paused || self.pause_menu.can_resume()) {
+            self.set_paused(!self.is_paused);
+            return;
+        }
+
+        // Freeze everything while paused
+        if self.is_paused {
+            self.update_pause_menu(rl);
+            return;
+        }
+        self.update_input_settings();
 
         // Toggle debug mode
         if rl.is_key_pressed(KEY_TAB) { 
             self.debug_mode = !self.debug_mode; 
         }
 
+        // Open the dev console
+        if rl.is_key_pressed(KEY_GRAVE) {
+            self.console.is_open = true;
+            return;
+        }
+
+        self.step(rl.get_time(), rl.get_frame_time());
+    }
+
+    fn draw(&mut self, canvas: &mut Canvas){
+        let pause_elements = self.pause_menu.get_elements(canvas);
+        let frame_time = canvas.get_frame_time();
+        let console_elements = self.console.get_elements(canvas);
+        let mut power_up_elements = ScreenElements::new(canvas, self.get_power_up_texts(), vec![], vec![]);
+        power_up_elements.shapes = self.get_power_up_shapes();
+        
+        // Clear screen
+        canvas.clear_background(Color::BLACK);
+
+        // Draw score text
+        let text = self.get_hud_text();
+        let centralized_x = SCREEN_SIZE.x / 2.0 - (measure_text(&text, 22) as f32 / 2.0);
+        canvas.draw_text(&text, centralized_x as i32, (SCREEN_SIZE.y * 0.01) as i32, 22, self.score_color);
+        
+        // Draw the stage under everything else
+        for shape in self.rules.stage.get_shapes(self.stats.time_played, Rectangle::new(0.0, 0.0, SCREEN_SIZE.x, SCREEN_SIZE.y)) {
+            shape.draw(canvas);
+        }
+
+        // Draw game objects, ghosted paddles faded out
+        power_up_elements.draw(canvas);
+        if self.ball.is_active { 
+            canvas.draw_circle_v(self.ball.position, self.ball.radius, self.ball.color);
+        }
+        for (paddle, is_left) in [(&self.left_paddle, true), (&self.right_paddle, false)] {
+            let color = if self.is_ghosted(is_left) { paddle.color.fade(0.2) } else { paddle.color };
+            canvas.draw_rectangle_rec(&paddle.hitbox, color);
+        }
+
+        // Draw debug info
+        if self.debug_mode {
+            let stats = self.get_debug_info(frame_time);
+            for shape in self.get_hitbox_display() { shape.draw(canvas); }
+            for shape in self.get_input_display() { shape.draw(canvas); }
+            canvas.draw_fps(0, 0);
+            canvas.draw_text(&stats, 0, (SCREEN_SIZE.y * 0.05) as i32, 18, Color::GREEN);
+        }
+
+        // Draw console at the bottom of the screen
+        if self.console.is_open {
+            let top = SCREEN_SIZE.y * 0.58;
+            canvas.draw_rectangle(0, top as i32, SCREEN_SIZE.x as i32, (SCREEN_SIZE.y - top) as i32, Color::new(0, 0, 0, 200));
+            console_elements.draw(canvas);
+        }
+
+        // Draw pause menu over the frozen game
+        if self.is_paused {
+            canvas.draw_rectangle(0, 0, SCREEN_SIZE.x as i32, SCREEN_SIZE.y as i32, Color::new(0, 0, 0, 180));
+            pause_elements.draw(canvas);
+        }
+    }
+
+    fn is_active(&self) -> bool { return self.is_active; }
+    fn get_music_level(&self) -> Option<i32> {
+        if self.is_paused { return None; }
+        return Some(self.tier);
+    }
+    fn get_next_scene(&self, _rl: &RaylibHandle) -> Box<dyn GameScene> { 
+        let devices = self.get_player_devices();
+        
+        match self.next_scene {
+            SceneType::WinnerScene => return Box::new(WinnerScene::new(self.rules.clone(), self.match_score, devices)),
+            SceneType::ResultsScene => return Box::new(ResultsScene::new(self.game_mode, self.difficulty, self.rules.clone(), self.stats, devices)),
+            SceneType::GameLoop => return Box::new(GameLoop::new(self.game_mode, self.rules.clone(), devices, self.hiscore, Settings::load().input)),
+            SceneType::MainMenu => return Box::new(MainMenu::new()),
+        }
+    }
+}
+
+impl GameLoop {
+    // Advances the game rules by a frame, without touching raylib
+    pub fn step(self: &mut Self, time: f64, dt: f32) {
+        let ball_input = self.players_input[0].sample(time, dt);
+        let paddle_input = self.players_input[1].sample(time, dt);
+        self.stats.time_played += dt;
+
+        if self.game_mode.get_time_limit().is_some_and(|limit| self.stats.time_played >= limit) {
+            self.end_game();
+            return;
+        }
+
         // Respawn ball if outside of the screen
         self.ball.is_active = self.ball.position.x > 0.0 && self.ball.position.x <= SCREEN_SIZE.x;
+        if !self.ball.is_active && self.has_power_up(PowerUp::Shield) {
+            self.spawn_ball();
+            return;
+        }
         if !self.ball.is_active {
-            self.respawn_player(&rl);
+            self.respawn_player(dt);
             return;
         }
 
@@ -30,61 +160,198 @@ impl GameScene for GameLoop {
         }
 
         // Update ball and it references
-        self.ball.update(&rl, &ball_input);
+        let ball_start = self.ball.position;
+        self.ball.update(dt, &ball_input);
+
+        // Zones and bumper kicks change how far the ball goes
+        let speed_scale = self.rules.stage.get_speed_scale(ball_start) * (1.0 + self.ball_kick);
+        self.ball.position = ball_start + (self.ball.position - ball_start) * speed_scale;
+        self.ball.velocity *= speed_scale;
+        self.ball_kick = (self.ball_kick - KICK_DECAY * dt).max(0.0);
+
         self.left_paddle.player_pos = self.ball.position;
         self.right_paddle.player_pos = self.ball.position;
         
         // Update paddles
-        self.left_paddle.update(&rl, &paddle_input);
-        self.right_paddle.update(&rl, &paddle_input);
-        self.check_ball_collisions(&ball_input);
+        self.left_paddle.update(dt, &paddle_input);
+        self.right_paddle.update(dt, &paddle_input);
+        self.check_ball_collisions(&ball_input, ball_start);
+        if self.rules.versus.is_some_and(|versus| self.score >= versus.rally_target) {
+            self.end_round(true);
+            return;
+        }
+        self.check_portals();
+        self.update_power_ups(dt);
     }
 
-    fn draw(&mut self, rl: &mut RaylibHandle, thread: &RaylibThread){
-        // Clear screen
-        let mut draw_handle = rl.begin_drawing(thread);
-        draw_handle.clear_background(Color::BLACK);
+    // Teleports the ball once when its center gets in a portal
+    fn check_portals(self: &mut Self) {
+        match self.rules.stage.get_portal_exit(self.ball.position) {
+            Some(exit) if !self.in_portal => {
+                self.ball.position = exit;
+                self.in_portal = true;
+            },
+            Some(_) => {},
+            None => self.in_portal = false,
+        }
+    }
 
-        // Draw score text
-        let text = format!("Hiscore: {}\n Score: {}", self.hiscore, self.score);
-        let centralized_x = SCREEN_SIZE.x / 2.0 - (measure_text(&text, 22) as f32 / 2.0);
-        draw_handle.draw_text(&text, centralized_x as i32, (SCREEN_SIZE.y * 0.01) as i32, 22, self.score_color);
-        
-        // Draw game objects
-        if self.ball.is_active { 
-            draw_handle.draw_circle_v(self.ball.position, self.ball.radius, self.ball.color);
+    // Pauses when a player's device is unplugged, and switches to it again once it's back
+    fn update_devices(self: &mut Self) {
+        if self.devices.update().is_empty() { return; }
+
+        let players_amount = self.get_players_amount();
+        let mut taken: Vec<DeviceId> = self.players_input.iter().map(|p| p.get_device_id()).collect();
+        let mut missing_device = None;
+
+        for i in 0..players_amount {
+            let id = self.players_input[i].get_device_id();
+            if self.devices.is_connected(&id) { continue; }
+
+            match self.devices.find_reconnected(&id, &taken) {
+                Some(new_id) => {
+                    self.players_input[i].set_device(DeviceRegistry::create_device(&new_id));
+                    if players_amount == 1 { self.players_input[1].set_device(DeviceRegistry::create_device(&new_id)); }
+                    taken.push(new_id);
+                },
+                None => {
+                    missing_device = Some(id.get_name());
+                    self.set_paused(true);
+                }
+            }
         }
-        draw_handle.draw_rectangle_rec(&self.left_paddle.hitbox, &self.left_paddle.color);
-        draw_handle.draw_rectangle_rec(&self.right_paddle.hitbox, &self.right_paddle.color);
 
-        // Draw debug info
-        if self.debug_mode {
-            let stats = self.get_debug_info();
-            draw_handle.draw_fps(0, 0);
-            draw_handle.draw_text(&stats, 0, (SCREEN_SIZE.y * 0.05) as i32, 18, Color::GREEN);
+        self.pause_menu.set_missing_device(missing_device);
+    }
+
+    // In online play the peer is told too, so both sides freeze together
+    fn set_paused(self: &mut Self, is_paused: bool) {
+        if is_paused == self.is_paused { return; }
+        self.is_paused = is_paused;
+        if let Some(network) = &mut self.network { network.send_pause(is_paused); }
+    }
+
+    // The input settings can be changed from the pause menu, so they're applied when it's left
+    fn update_input_settings(self: &mut Self) {
+        if Settings::get_revision() == self.settings_revision { return; }
+        self.settings_revision = Settings::get_revision();
+
+        let settings = Settings::load().input;
+        for (i, player) in self.players_input.iter_mut().enumerate() {
+            player.set_defaults(settings.snapness[i], settings.socd_modes[i]);
         }
     }
 
-    fn is_active(&self) -> bool { return self.is_active; }
-    fn get_next_scene(&self, _rl: &RaylibHandle) -> Box<dyn GameScene> { return Box::new(MainMenu::new()); }
-}
+    // Player 2 only mirrors player 1's device in singleplayer
+    fn get_players_amount(self: &Self) -> usize {
+        return if self.game_mode == GameMode::Multiplayer { 2 } else { 1 };
+    }
 
-impl GameLoop {
-    fn check_ball_collisions(self: &mut Self, ball_input: &InputData) {
-        let hit_vertical_edge = self.ball.position.y == self.ball.radius || self.ball.position.y == SCREEN_SIZE.y - self.ball.radius;
-        let hit_paddle = self.right_paddle.hitbox.check_collision_circle_rec(self.ball.position, self.ball.radius + 5.0) ||
-                         self.left_paddle.hitbox.check_collision_circle_rec(self.ball.position, self.ball.radius + 5.0);
-        
-        if hit_paddle { self.paddle_bounce(ball_input); }
-        if hit_vertical_edge { self.edge_bounce(ball_input); }
+    // Smoothed, so the number can be read while it's changing every frame
+    fn update_input_lag(self: &mut Self, present_time: f64) {
+        for (lag, player) in self.input_lag.iter_mut().zip(&self.players_input) {
+            let sample_time = player.get_last_data().sample_time;
+            *lag = lerp(*lag, ((present_time - sample_time) * 1000.0) as f32, 0.1);
+        }
+    }
+
+    fn update_pause_menu(self: &mut Self, rl: &RaylibHandle) {
+        let mut devices: Vec<&mut dyn InputDevice> = self.players_input.iter_mut().map(|p| p.get_device()).collect();
+        match self.pause_menu.update(rl, &mut devices) {
+            PauseAction::Resume => self.set_paused(false),
+            PauseAction::Restart => {
+                self.next_scene = SceneType::GameLoop;
+                self.is_active = false;
+            },
+            // Zen only ends when leaving, so that's when it gets its results
+            PauseAction::Quit if self.game_mode == GameMode::Zen => self.end_game(),
+            PauseAction::Quit => {
+                self.next_scene = SceneType::MainMenu;
+                self.is_active = false;
+            },
+            PauseAction::None => {}
+        }
+    }
+
+    // Sweeps the ball along its path this frame, bouncing off whatever it touches first.
+    // It can't pass through anything on a fast frame, and only bounces once per contact
+    fn check_ball_collisions(self: &mut Self, ball_input: &InputData, ball_start: Vector2) {
+        let mut start = ball_start;
+
+        for _ in 0..MAX_BOUNCES_PER_FRAME {
+            let end = self.ball.position;
+            let first_hit = self.get_ball_colliders().into_iter()
+                .filter_map(|(contact, collider, reach)| {
+                    collider.sweep_circle(start, end, self.ball.radius + reach).map(|hit| (contact, hit))
+                })
+                .min_by(|a, b| a.1.time.total_cmp(&b.1.time));
+
+            let Some((contact, hit)) = first_hit else { return; };
+            self.ball.position = hit.reflect(end);
+            start = hit.position;
+
+            match contact {
+                Contact::Edge => self.edge_bounce(ball_input),
+                Contact::Paddle => self.paddle_hit(hit, ball_input),
+                Contact::Obstacle => self.obstacle_bounce(hit),
+                Contact::Bumper(kick) => self.bumper_bounce(hit, kick),
+            }
+        }
+    }
+
+    // Everything the ball can bounce on, with how far past its radius it reaches them
+    fn get_ball_colliders(self: &Self) -> Vec<(Contact, Collider, f32)> {
+        let mut colliders = vec![
+            (Contact::Edge, Collider::Wall(Vector2::zero(), Vector2::new(0.0, 1.0)), 0.0),
+            (Contact::Edge, Collider::Wall(Vector2::new(0.0, SCREEN_SIZE.y), Vector2::new(0.0, -1.0)), 0.0),
+        ];
+
+        if !self.is_ghosted(true) { colliders.push((Contact::Paddle, Collider::Rect(self.left_paddle.hitbox), PADDLE_REACH)); }
+        if !self.is_ghosted(false) { colliders.push((Contact::Paddle, Collider::Rect(self.right_paddle.hitbox), PADDLE_REACH)); }
+
+        for (collider, kick) in self.rules.stage.get_colliders(self.stats.time_played) {
+            let contact = if kick > 0.0 { Contact::Bumper(kick) } else { Contact::Obstacle };
+            colliders.push((contact, collider, 0.0));
+        }
+        return colliders;
+    }
+
+    // Faces return the ball, while the caps knock it away up or down
+    fn paddle_hit(self: &mut Self, hit: Hit, ball_input: &InputData) {
+        if hit.normal.x.abs() >= hit.normal.y.abs() { 
+            self.paddle_bounce(hit, ball_input);
+            return;
+        }
+
+        let angle = self.ball.prone_dir.y.abs().max(self.tunables.paddle_min_angle);
+        self.ball.prone_dir.y = angle * hit.normal.y.signum();
+    }
+
+    // Obstacles just mirror the ball's direction
+    fn obstacle_bounce(self: &mut Self, hit: Hit) {
+        let prone_dir = self.ball.prone_dir;
+        self.ball.prone_dir = prone_dir - hit.normal * 2.0 * prone_dir.dot(hit.normal);
+        self.players_input[0].override_last_dir(Vector2::zero());
+    }
+
+    // Bumpers send the ball off their curve and kick it, keeping it crossing the arena at the same pace
+    fn bumper_bounce(self: &mut Self, hit: Hit, kick: f32) {
+        self.obstacle_bounce(hit);
+
+        let prone_dir = self.ball.prone_dir;
+        let max_angle = self.tunables.paddle_max_angle * 1.5;
+        self.ball.prone_dir = Vector2::new(prone_dir.x.signum(), (prone_dir.y / prone_dir.x.abs().max(0.25)).clamp(-max_angle, max_angle));
+        self.ball_kick = kick;
     }
 
     // Bounce ball when hit top or bottom screen
     fn edge_bounce(self: &mut Self, ball_input: &InputData) {
+        AudioPlayer::play(SoundEffect::EdgeBounce);
         let entry_angle = self.ball.velocity.normalized().y.abs();
 
         // Calculates out angle exponentially
-        let mut new_angle = entry_angle.powf(1.65).clamp(0.4, 0.55);
+        let tunables = self.tunables;
+        let mut new_angle = entry_angle.powf(tunables.edge_exponent).clamp(tunables.edge_min_angle, tunables.edge_max_angle);
         new_angle *= -self.ball.velocity.y.signum();
 
         // Height down player horizontal input
@@ -97,24 +364,18 @@ impl GameLoop {
     }
 
     // Bounce ball when hits a paddle
-    fn paddle_bounce(self: &mut Self, ball_input: &InputData)
+    fn paddle_bounce(self: &mut Self, hit: Hit, ball_input: &InputData)
     {
-        let close_to_edge = self.ball.position.y >= SCREEN_SIZE.y - self.ball.radius - 73.0 || 
-                            self.ball.position.y <= self.ball.radius + 73.0;
-
-        let mut new_angle: f32 = thread_rng().gen_range(0.45..1.0);
-        if close_to_edge { new_angle *= 1.5; }
-           
-        // Decides new angle signum
-        if close_to_edge || self.bounced_vertically || ball_input.raw_dir.y == 0.0 { 
-            new_angle *= self.ball.prone_dir.y.signum();
-        }
-        else { new_angle *= ball_input.raw_dir.y.signum(); }
-
-        // Move ball out of the paddles
-        let min = self.left_paddle.position.x + PADDLE_SIZE.x + self.ball.radius;
-        let max = self.right_paddle.position.x - PADDLE_SIZE.x - self.ball.radius;
-        self.ball.position = self.ball.position.clamp(min, max);
+        AudioPlayer::play(SoundEffect::PaddleBounce);
+        let new_angle = match self.rules.bounce_model {
+            BounceModel::Random => self.get_random_angle(ball_input, hit.position),
+            BounceModel::English => {
+                let paddle = if self.ball.prone_dir.x > 0.0 { &self.right_paddle } else { &self.left_paddle };
+                let half_height = paddle.size.y / 2.0 + self.ball.radius + PADDLE_REACH;
+                let offset = (hit.position.y - (paddle.position.y + paddle.size.y / 2.0)) / half_height;
+                get_english_angle(offset, paddle.velocity / paddle.speed, &self.tunables)
+            },
+        };
 
         // Update paddles
         self.left_paddle.is_active = !self.left_paddle.is_active;
@@ -124,95 +385,273 @@ impl GameLoop {
         self.ball.prone_dir.x *= -1.0;
         self.ball.prone_dir.y = new_angle;
         
-        self.score += 1;
-        self.update_difficulty();
+        self.add_score();
+        self.stats.rally += 1;
+        self.stats.total_bounces += 1;
+        self.stats.longest_rally = self.stats.longest_rally.max(self.stats.rally);
 
         self.bounced_vertically = false;
         self.players_input[0].override_last_dir(Vector2::zero());
     }
     
-    fn respawn_player(self: &mut Self, rl: &RaylibHandle) {
+    // Random slope, steeper near the edges, going where the ball's player is holding.
+    // The ball has already moved on after the reflection, so it's measured where it touched
+    fn get_random_angle(self: &mut Self, ball_input: &InputData, contact: Vector2) -> f32 {
+        let tunables = self.tunables;
+        let close_to_edge = contact.y >= SCREEN_SIZE.y - self.ball.radius - tunables.paddle_edge_zone || 
+                            contact.y <= self.ball.radius + tunables.paddle_edge_zone;
+
+        let mut new_angle = lerp(tunables.paddle_min_angle, tunables.paddle_max_angle, self.rng.gen::<f32>());
+        if close_to_edge { new_angle *= 1.5; }
+           
+        // Decides new angle signum
+        if close_to_edge || self.bounced_vertically || ball_input.raw_dir.y == 0.0 { 
+            new_angle *= self.ball.prone_dir.y.signum();
+        }
+        else { new_angle *= ball_input.raw_dir.y.signum(); }
+
+        return new_angle;
+    }
+
+    fn respawn_player(self: &mut Self, dt: f32) {
+        if self.respawn_timer == 0.0 { AudioPlayer::play(SoundEffect::LifeLost); }
+
         // Wait for 1 second
-        self.respawn_timer += rl.get_frame_time();
+        self.respawn_timer += dt;
         if self.respawn_timer < 1.0 { return; }
 
         // Reset variables
+        self.clear_power_ups();
         self.ball.position = SCREEN_SIZE / 2.0;
-        self.left_paddle.position.y =  SCREEN_SIZE.y / 2.0 - PADDLE_SIZE.y / 2.0;
-        self.right_paddle.position.y =  SCREEN_SIZE.y / 2.0 - PADDLE_SIZE.y / 2.0;
+        self.left_paddle.position.y =  SCREEN_SIZE.y / 2.0 - self.left_paddle.size.y / 2.0;
+        self.right_paddle.position.y =  SCREEN_SIZE.y / 2.0 - self.right_paddle.size.y / 2.0;
 
         self.players_input[0].override_last_dir(Vector2::zero());
         self.ball.prone_dir = Vector2 { x: -1.0, y: 0.0 };
+        self.ball_kick = 0.0;
         
         // Check for a new highscore
         if self.score > self.hiscore { 
-            MiscUtils::save_highscore(self.score);
             self.hiscore = self.score;
         }
+        self.stats.rally = 0;
+        self.stats.best_score = self.stats.best_score.max(self.score);
+        self.stats.best_checkpoint = self.stats.best_checkpoint.max(self.checkpoint);
+
+        // Versus never runs out of lives, the paddle player just takes the round
+        if self.rules.versus.is_some() {
+            self.end_round(false);
+            return;
+        }
         
-        // Reset checkpoint if lose all lives 
-        if self.ball.lives <= 1 { 
-            self.ball.prone_dir = Vector2::zero();
-            self.ball.radius += 1.6; 
-            self.ball.lives = 3;
-            self.checkpoint = 0;
-        }
-        else { 
+        // Game over if lose all lives 
+        if self.game_mode.loses_lives() {
+            if self.ball.lives <= 1 { 
+                self.is_active = false;
+                return;
+            }
+
             self.ball.lives -= 1; 
-            self.ball.radius -= 0.8;
+            self.ball.radius -= LIFE_RADIUS;
         }
         
         self.ball.is_active = true;
         self.left_paddle.is_active = true;
         self.right_paddle.is_active = false;
 
-        self.score = self.checkpoint;
+        // Time attack counts every bounce, the rest go back to the last checkpoint
+        if self.game_mode != GameMode::TimeAttack { self.score = self.checkpoint; }
         self.respawn_timer = 0.0;
         self.update_difficulty();
     }
 
-    fn get_debug_info(self: &Self) -> String {
-        // let mut stats = format!("- Prone: ({:.2}, {:.2}) \n- Move: ({:.2}, {:.2}\n", 
-        //                 self.ball.prone_dir.x, self.ball.prone_dir.y, 
-        //                 ball_input.dir.x, ball_input.dir.y);
-    
-        // if ball_input.on_gamepad {
-        //    stats += &format!(" {} \n ({:.2}, {:.2})", input.gamepad_name, 
-        //             input.raw_dir.x, input.raw_dir.y);
-        // }
-        
-        // return stats;
+    // Score and whatever else the game mode is played around
+    fn get_hud_text(self: &Self) -> String {
+        if let Some(versus) = self.rules.versus { return self.get_versus_hud_text(&versus); }
 
-        todo!("Fix this and made so that every localplayer information in show");
+        match self.game_mode {
+            GameMode::TimeAttack => {
+                let time_left = (self.game_mode.get_time_limit().unwrap_or(0.0) - self.stats.time_played).max(0.0);
+                return format!("Hiscore: {}\n Score: {}\n  Time: {:.0}", self.hiscore, self.score, time_left.ceil());
+            },
+            GameMode::Survival => return format!("Hiscore: {}\n Score: {}\n  Tier: {}", self.hiscore, self.score, self.tier + 1),
+            GameMode::Zen => return format!("Best: {}\nScore: {}", self.hiscore, self.score),
+            _ => return format!("Hiscore: {}\n Score: {}", self.hiscore, self.score),
+        }
+    }
+
+    // Goes to the results with the current score, without waiting to lose the ball
+    fn end_game(self: &mut Self) {
+        self.stats.best_score = self.stats.best_score.max(self.score);
+        self.stats.best_checkpoint = self.stats.best_checkpoint.max(self.checkpoint);
+        self.next_scene = SceneType::ResultsScene;
+        self.is_active = false;
+    }
+
+    // Adds a point at a time, so multiplied scores still reach each checkpoint
+    pub fn add_score(self: &mut Self) {
+        for _ in 0..self.get_score_multiplier() {
+            self.score += 1;
+            self.update_difficulty();
+        }
+    }
+
+    // Puts the ball back in the middle without losing a life
+    pub fn spawn_ball(self: &mut Self) {
+        self.ball.position = SCREEN_SIZE / 2.0;
+        self.ball.prone_dir = Vector2 { x: -1.0, y: 0.0 };
+        self.ball_kick = 0.0;
+        self.ball.is_active = true;
+        self.left_paddle.is_active = true;
+        self.right_paddle.is_active = false;
+
+        self.respawn_timer = 0.0;
+        self.players_input[0].override_last_dir(Vector2::zero());
+    }
+
+    // Each player's device, input directions and held buttons, like a fighting game input display,
+    // followed by the state of the game objects
+    fn get_debug_info(self: &Self, frame_time: f32) -> String {
+        let mut info = String::new();
+
+        for i in 0..self.get_players_amount() {
+            let data = self.players_input[i].get_last_data();
+            let held = [(data.is_up_down, "U"), (data.is_down_down, "D"), (data.is_left_down, "L"), (data.is_right_down, "R")];
+            let buttons: Vec<&str> = held.iter().map(|(down, name)| if *down { *name } else { "-" }).collect();
+
+            info += &format!("P{} - {}\n", i + 1, self.players_input[i].get_device_id().get_name());
+            info += &format!("  Raw: ({:.2}, {:.2})  Dir: ({:.2}, {:.2})\n", data.raw_dir.x, data.raw_dir.y, data.dir.x, data.dir.y);
+            info += &format!("  Buttons: {}  Lag: {:.1}ms\n", buttons.join(" "), self.input_lag[i]);
+            info += &Self::get_socd_info(&data);
+        }
+
+        let ball = &self.ball;
+        info += &format!("Ball - Prone: ({:.2}, {:.2})\n", ball.prone_dir.x, ball.prone_dir.y);
+        info += &format!("  Vel: ({:.0}, {:.0})  Speed: {:.0}\n", ball.velocity.x, ball.velocity.y, ball.speed);
+
+        for (name, paddle) in [("Left", &self.left_paddle), ("Right", &self.right_paddle)] {
+            info += &format!("{} Paddle - Vel: {:.0}  Range: {:.2}  Close: {:.2}\n", name, paddle.velocity,
+                             paddle.view_range, paddle.get_player_pos_closeness());
+        }
+
+        info += &format!("Tier: {}  Frame: {:.1}ms  Played: {:.0}s", self.tier, frame_time * 1000.0, self.stats.time_played);
+        return info;
+    }
+
+    // Opposing directions held and what the SOCD mode made of them
+    fn get_socd_info(data: &InputData) -> String {
+        let Some(mode) = data.socd_mode else { return "  SOCD: - (axis)\n".to_string(); };
+
+        let mut opposing = Vec::new();
+        if data.is_left_down && data.is_right_down { opposing.push("L+R"); }
+        if data.is_up_down && data.is_down_down { opposing.push("U+D"); }
+        let held = if opposing.is_empty() { "-".to_string() } else { opposing.join(" ") };
+
+        return format!("  SOCD: {}  {} -> ({:.0}, {:.0})\n", mode.get_name(), held, data.raw_dir.x, data.raw_dir.y);
+    }
+
+    // Collision shapes, the edges the ball bounces on, and how far each paddle sees
+    fn get_hitbox_display(self: &Self) -> Vec<Shape> {
+        let color = Color::GREEN;
+        let top = self.ball.radius;
+        let bottom = SCREEN_SIZE.y - self.ball.radius;
+
+        let mut shapes = vec![
+            Shape::CircleLines(self.ball.position, self.ball.radius + 5.0, color),
+            Shape::Line(self.ball.position, self.ball.position + self.ball.velocity * 0.2, color),
+            Shape::Line(Vector2::new(0.0, top), Vector2::new(SCREEN_SIZE.x, top), Color::DARKGREEN),
+            Shape::Line(Vector2::new(0.0, bottom), Vector2::new(SCREEN_SIZE.x, bottom), Color::DARKGREEN),
+        ];
+
+        // Paddles watch the ball from their side towards the middle
+        for (paddle, side) in [(&self.left_paddle, 1.0), (&self.right_paddle, -1.0)] {
+            let view_x = paddle.position.x + SCREEN_SIZE.x * paddle.view_range * side;
+            shapes.push(Shape::RectangleLines(paddle.hitbox, color));
+            shapes.push(Shape::Line(Vector2::new(view_x, 0.0), Vector2::new(view_x, SCREEN_SIZE.y), paddle.colors[1]));
+        }
+
+        for (collider, _) in self.rules.stage.get_colliders(self.stats.time_played) {
+            match collider {
+                Collider::Rect(rect) => shapes.push(Shape::RectangleLines(rect, color)),
+                Collider::Circle(center, radius) => shapes.push(Shape::CircleLines(center, radius, color)),
+                Collider::Wall(_, _) => {},
+            }
+        }
+
+        return shapes;
+    }
+
+    // A stick with the raw (gray) and smoothed (gold) directions, surrounded by the held buttons
+    fn get_input_display(self: &Self) -> Vec<Shape> {
+        let radius = 30.0;
+        let mut shapes = Vec::new();
+
+        for i in 0..self.get_players_amount() {
+            let data = self.players_input[i].get_last_data();
+            let x = if i == 0 { radius * 2.0 } else { SCREEN_SIZE.x - radius * 2.0 };
+            let center = Vector2::new(x, SCREEN_SIZE.y - radius * 2.0);
+
+            shapes.push(Shape::CircleLines(center, radius, Color::GRAY));
+            shapes.push(Shape::Line(center, center + data.dir * radius, Color::GOLD));
+            shapes.push(Shape::Circle(center + data.raw_dir * radius, 3.0, Color::GRAY));
+            shapes.push(Shape::Circle(center + data.dir * radius, 4.0, Color::GOLD));
+
+            let buttons = [(data.is_right_down, Vector2::new(1.0, 0.0)), (data.is_left_down, Vector2::new(-1.0, 0.0)),
+                           (data.is_down_down, Vector2::new(0.0, 1.0)), (data.is_up_down, Vector2::new(0.0, -1.0))];
+            for (is_down, offset) in buttons {
+                let pos = center + offset * (radius + 10.0);
+                let rect = Rectangle::new(pos.x - 4.0, pos.y - 4.0, 8.0, 8.0);
+                shapes.push(if is_down { Shape::Rectangle(rect, Color::GOLD) } else { Shape::RectangleLines(rect, Color::GRAY) });
+            }
+        }
+
+        return shapes;
     }
     
-    pub fn new(selected_mode: GameMode, selected_devices: (Box<dyn InputDevice>, Box<dyn InputDevice>)) -> GameLoop {
+    // The hiscore and input settings are given by the caller, so tests don't depend on the saved ones
+    pub fn new(selected_mode: GameMode, rules: GameRules, selected_devices: (Box<dyn InputDevice>, Box<dyn InputDevice>),
+               hiscore: i32, input_settings: InputSettings) -> GameLoop {
         // Just in case
         if selected_mode == GameMode::None {
             panic!("GameMode wasn't selected. How did you manage to do this?");
         }
+        let paddle_padding = rules.stage.paddle_padding;
         
-        return GameLoop {
+        let mut game = GameLoop {
              score: 0,
+             tier: 0,
              checkpoint: 0,
+             stats: GameStats::default(),
              respawn_timer: 0.0,
  
-             hiscore: MiscUtils::get_highscore(),
+             hiscore,
              game_mode: selected_mode,
+             difficulty: Difficulty::Normal,
+             rules,
              score_color: Color::DARKGREEN,
  
              is_active: true,
+             is_paused: false,
+             network: None,
+             pause_menu: PauseMenu::new(),
+             devices: DeviceRegistry::new(),
+             settings_revision: Settings::get_revision(),
+             next_scene: SceneType::ResultsScene,
              debug_mode: false,
+             input_lag: [0.0; 2],
+             console: DevConsole::new(),
+             tunables: Tunables::new(),
+             rng: StdRng::from_entropy(),
              bounced_vertically: false,
              
              players_input: vec![
                  // Player 1
                  // PlayerInput::new(0, Box::new(GamepadInput::new(0, true)), 3.0, true),
-                 PlayerInput::new(0, selected_devices.0, 3.0, true),
+                 PlayerInput::new(0, selected_devices.0, input_settings.snapness[0], input_settings.socd_modes[0]),
  
                  // Player 2
                  // PlayerInput::new(1, Box::new(KeyboardInput::new()), 7.0, false),
-                 PlayerInput::new(1, selected_devices.1, 7.0, false) 
+                 PlayerInput::new(1, selected_devices.1, input_settings.snapness[1], input_settings.socd_modes[1])
              ],
              
              ball: Ball::new(
@@ -225,7 +664,7 @@ impl GameLoop {
  
              left_paddle: Paddle::new(
                  Vector2 { 
-                     x: PADDLE_PADDING, 
+                     x: paddle_padding, 
                      y: SCREEN_SIZE.y / 2.0 - PADDLE_SIZE.y / 2.0 
                  }, [
                      Color::new(255, 105, 97, 130), // Player is far - #FF6961
@@ -235,9 +674,16 @@ impl GameLoop {
                  selected_mode == GameMode::Multiplayer, true
              ), 
  
+             paddle_speed: INITIAL_PADDLE_SPEED,
+             ball_kick: 0.0,
+             in_portal: false,
+             pickups: Vec::new(),
+             pickup_timer: PICKUP_INTERVAL.0,
+             active_power_ups: Vec::new(),
+             match_score: MatchScore::default(),
              right_paddle: Paddle::new(
                  Vector2 { 
-                     x: SCREEN_SIZE.x - PADDLE_SIZE.x - PADDLE_PADDING, 
+                     x: SCREEN_SIZE.x - PADDLE_SIZE.x - paddle_padding, 
                      y: SCREEN_SIZE.y / 2.0 - PADDLE_SIZE.y / 2.0 
                  }, [
                      Color::new(255, 105, 97, 130), // Player is far - #FF6961
@@ -247,62 +693,270 @@ impl GameLoop {
                  selected_mode == GameMode::Multiplayer, false
              ),
          };
+
+         game.ball.lives = selected_mode.get_lives();
+         return game;
      }
 
-     fn update_difficulty(self: &mut Self) {
-        match self.score {
+     pub fn update_difficulty(self: &mut Self) {
+        let Some(tier) = DIFFICULTY_CHECKPOINTS.iter().position(|c| *c == self.score) else { return; };
+        let previous_tier = self.tier;
+        self.checkpoint = self.score;
+        self.tier = tier as i32;
+
+        match tier {
             0 => {
-                self.checkpoint = 0;
                 self.score_color = Color::DARKGREEN;
-                self.ball.speed = MAX_PLAYER_SPEED * 0.63;
+                self.ball.speed = self.tunables.max_player_speed * 0.63;
 
-                self.left_paddle.speed = INITIAL_PADDLE_SPEED;
-                self.right_paddle.speed = INITIAL_PADDLE_SPEED;
-                self.left_paddle.view_range = INITIAL_PADDLE_RANGE;
-                self.right_paddle.view_range = INITIAL_PADDLE_RANGE;
+                self.paddle_speed = INITIAL_PADDLE_SPEED;
+                self.left_paddle.view_range = self.tunables.paddle_range;
+                self.right_paddle.view_range = self.tunables.paddle_range;
             },
 
-            10 => {
-                self.checkpoint = 10;
+            1 => {
                 self.score_color = Color::GREEN;
-                self.ball.speed = MAX_PLAYER_SPEED * 0.75;
+                self.ball.speed = self.tunables.max_player_speed * 0.75;
 
-                self.left_paddle.speed = INITIAL_PADDLE_SPEED * 0.9;
-                self.right_paddle.speed = INITIAL_PADDLE_SPEED * 0.9;
+                self.paddle_speed = INITIAL_PADDLE_SPEED * 0.9;
             },
 
-            25 => {
-                self.checkpoint = 25;
+            2 => {
                 self.score_color = Color::YELLOW;
-                self.ball.speed = MAX_PLAYER_SPEED * 0.85;
+                self.ball.speed = self.tunables.max_player_speed * 0.85;
 
-                self.left_paddle.speed = INITIAL_PADDLE_SPEED * 0.8;
-                self.right_paddle.speed = INITIAL_PADDLE_SPEED * 0.8;
-                self.left_paddle.view_range = INITIAL_PADDLE_RANGE * 0.8;
-                self.right_paddle.view_range = INITIAL_PADDLE_RANGE * 0.8;
+                self.paddle_speed = INITIAL_PADDLE_SPEED * 0.8;
+                self.left_paddle.view_range = self.tunables.paddle_range * 0.8;
+                self.right_paddle.view_range = self.tunables.paddle_range * 0.8;
             },
 
-            50 => {
-                self.checkpoint = 50;
+            3 => {
                 self.score_color = Color::GOLD;
-                self.ball.speed = MAX_PLAYER_SPEED * 0.90;
+                self.ball.speed = self.tunables.max_player_speed * 0.90;
 
-                self.left_paddle.speed = INITIAL_PADDLE_SPEED * 0.6;
-                self.right_paddle.speed = INITIAL_PADDLE_SPEED * 0.6;
-                self.left_paddle.view_range = INITIAL_PADDLE_RANGE * 0.75;
-                self.right_paddle.view_range = INITIAL_PADDLE_RANGE * 0.75;
+                self.paddle_speed = INITIAL_PADDLE_SPEED * 0.6;
+                self.left_paddle.view_range = self.tunables.paddle_range * 0.75;
+                self.right_paddle.view_range = self.tunables.paddle_range * 0.75;
             }
 
-            75 => {
-                self.checkpoint = 75;
+            _ => {
                 self.score_color = Color::RED;
-                self.ball.speed = MAX_PLAYER_SPEED;
-                self.left_paddle.speed = INITIAL_PADDLE_SPEED * 0.5;
-                self.right_paddle.speed = INITIAL_PADDLE_SPEED * 0.5;
-                self.left_paddle.view_range = INITIAL_PADDLE_RANGE * 0.6;
-                self.right_paddle.view_range = INITIAL_PADDLE_RANGE * 0.6;
-            } 
-            _=> {},
+                self.ball.speed = self.tunables.max_player_speed;
+                self.paddle_speed = INITIAL_PADDLE_SPEED * 0.5;
+                self.left_paddle.view_range = self.tunables.paddle_range * 0.6;
+                self.right_paddle.view_range = self.tunables.paddle_range * 0.6;
+            }
         }
+
+        // Survival still gets harder, but never saves a checkpoint
+        if self.game_mode == GameMode::Survival { self.checkpoint = 0; }
+
+        if self.tier > previous_tier { AudioPlayer::play(SoundEffect::TierUp); }
+        self.update_paddle_speed();
+        self.stats.max_tier = self.stats.max_tier.max(self.tier);
+    }
+}
+
+#[cfg(test)]
+impl GameLoop {
+    // Both players on scripts that hold nothing, swap in another device to drive them
+    pub fn new_scripted(game_mode: GameMode, rules: GameRules) -> GameLoop {
+        let script = ScriptedInput::new(false, vec![]);
+        return GameLoop::new(game_mode, rules, (Box::new(script.clone()), Box::new(script)), 0, InputSettings::default());
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Game with the ball heading to an edge, while player 1 holds a direction towards it
+    fn new_game(held: InputAction, ball_y: f32, prone_y: f32) -> GameLoop {
+        let mut game = GameLoop::new_scripted(GameMode::Singleplayer, GameRules::new());
+        game.players_input[0].set_device(Box::new(ScriptedInput::new(false, vec![(0.0, ScriptedFrame::new(&[held], Vector2::zero()))])));
+        game.ball.position = Vector2::new(SCREEN_SIZE.x * 0.5, ball_y);
+        game.ball.prone_dir = Vector2::new(-1.0, prone_y);
+        return game;
+    }
+
+    // Steps at 60fps until the ball bounces off an edge
+    fn run_until_bounce(game: &mut GameLoop) {
+        for frame in 1..=120 {
+            game.step(frame as f64 / 60.0, 1.0 / 60.0);
+            if game.bounced_vertically { return; }
+        }
+        panic!("The ball never reached the edge");
+    }
+
+    #[test]
+    fn ball_bounces_off_the_top_edge() {
+        let mut game = new_game(InputAction::Up, 60.0, -0.5);
+        run_until_bounce(&mut game);
+        assert!((0.4..=0.55).contains(&game.ball.prone_dir.y));
+    }
+
+    #[test]
+    fn ball_bounces_off_the_bottom_edge() {
+        let mut game = new_game(InputAction::Down, SCREEN_SIZE.y - 60.0, 0.5);
+        run_until_bounce(&mut game);
+        assert!((-0.55..=-0.4).contains(&game.ball.prone_dir.y));
+    }
+
+    // Ball level with the left paddle's middle and heading at it. Paddles don't move without input in multiplayer
+    fn new_paddle_game(speed: f32) -> GameLoop {
+        let mut game = GameLoop::new_scripted(GameMode::Multiplayer, GameRules::new());
+        game.ball.position = Vector2::new(150.0, game.left_paddle.hitbox.y + game.left_paddle.hitbox.height / 2.0);
+        game.ball.prone_dir = Vector2::new(-1.0, 0.0);
+        game.ball.speed = speed;
+        return game;
+    }
+
+    #[test]
+    fn fast_ball_doesnt_tunnel_through_paddles() {
+        // Each one moves the ball 250px in a single frame, past the whole paddle
+        for (speed, dt) in [(MAX_PLAYER_SPEED, 0.5), (MAX_PLAYER_SPEED * 2.0, 0.25), (MAX_PLAYER_SPEED * 30.0, 1.0 / 60.0)] {
+            let mut game = new_paddle_game(speed);
+            game.step(1.0, dt);
+
+            assert_eq!(game.score, 1);
+            assert_eq!(game.ball.prone_dir.x, 1.0);
+            assert!(game.ball.position.x > game.left_paddle.hitbox.x + game.left_paddle.hitbox.width);
+        }
+    }
+
+    #[test]
+    fn paddle_caps_knock_the_ball_away() {
+        let mut game = new_paddle_game(MAX_PLAYER_SPEED);
+        let paddle = game.left_paddle.hitbox;
+        game.ball.position = Vector2::new(paddle.x + paddle.width / 2.0, paddle.y - 60.0);
+        game.ball.prone_dir = Vector2::new(0.0, 1.0);
+        game.step(1.0, 0.2);
+
+        // Moved 100px, bouncing back up with whatever was left after touching the cap
+        let contact_y = paddle.y - game.ball.radius - PADDLE_REACH;
+        let remaining = 100.0 - (contact_y - (paddle.y - 60.0));
+        assert_eq!(game.score, 0);
+        assert!(game.ball.prone_dir.y < 0.0);
+        assert!((game.ball.position.y - (contact_y - remaining)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn edges_reflect_the_ball_by_its_penetration() {
+        // Confirm doesn't steer, so the ball only follows its prone_dir
+        let mut game = new_game(InputAction::Confirm, 100.0, -0.5);
+        let radius = game.ball.radius;
+        game.step(1.0, 1.0);
+
+        // Would have ended at 100 - 0.5 * speed, past the top edge
+        let overshoot = radius - (100.0 - 0.5 * game.ball.speed);
+        assert!((game.ball.position.y - (radius + overshoot)).abs() < 1e-3);
+        assert!(game.ball.prone_dir.y > 0.0);
+    }
+
+    #[test]
+    fn edges_bounce_once_per_contact() {
+        let mut game = new_game(InputAction::Up, 60.0, -0.5);
+        run_until_bounce(&mut game);
+        let bounce_dir = game.ball.prone_dir.y;
+
+        // The first frames after the bounce move away, even while pushing towards the edge
+        for frame in 1..=10 {
+            game.step(10.0 + frame as f64 / 60.0, 1.0 / 60.0);
+
+            assert_eq!(game.ball.prone_dir.y, bounce_dir);
+            assert!(game.ball.position.y > game.ball.radius);
+        }
+    }
+
+    #[test]
+    fn portals_teleport_the_ball_once() {
+        let mut game = new_paddle_game(MAX_PLAYER_SPEED);
+        game.rules.stage = Stage::parse("portal 0.2 0.5 0.8 0.5 0.05").unwrap();
+        game.ball.position = Vector2::new(SCREEN_SIZE.x * 0.2 + 20.0, SCREEN_SIZE.y * 0.5);
+        game.step(1.0, 1.0 / 60.0);
+
+        // Still inside the exit, so it doesn't go back
+        assert!((game.ball.position.x - SCREEN_SIZE.x * 0.8).abs() < 1e-3);
+        game.step(1.0, 1.0 / 60.0);
+        assert!(game.ball.position.x > SCREEN_SIZE.x * 0.7);
+    }
+
+    #[test]
+    fn zones_scale_the_ball_speed() {
+        let mut game = new_paddle_game(MAX_PLAYER_SPEED);
+        game.rules.stage = Stage::parse("zone 0 0 1 1 0.5").unwrap();
+        let start = game.ball.position;
+        game.step(1.0, 0.1);
+
+        assert!((start.x - game.ball.position.x - MAX_PLAYER_SPEED * 0.05).abs() < 1e-3);
+    }
+
+    // Ball past the left paddle, with the game already at a score of 12
+    fn new_mode_game(mode: GameMode) -> GameLoop {
+        let mut game = GameLoop::new_scripted(mode, GameRules::new());
+        game.run_command("tier 12");
+        game.ball.position = Vector2::new(-50.0, SCREEN_SIZE.y / 2.0);
+        return game;
+    }
+
+    #[test]
+    fn time_attack_keeps_its_score_and_ends_on_time() {
+        let mut game = new_mode_game(GameMode::TimeAttack);
+        game.step(1.0, 1.0);
+        assert_eq!((game.score, game.ball.lives), (12, 3));
+
+        game.step(2.0, 90.0);
+        assert!(!game.is_active);
+        assert!(game.next_scene == SceneType::ResultsScene);
+        assert_eq!(game.stats.best_score, 12);
+    }
+
+    #[test]
+    fn survival_ends_on_the_first_miss() {
+        let mut game = new_mode_game(GameMode::Survival);
+        assert_eq!(game.checkpoint, 0);
+
+        game.step(1.0, 1.0);
+        assert!(!game.is_active);
+    }
+
+    #[test]
+    fn zen_never_runs_out_of_lives() {
+        let mut game = new_mode_game(GameMode::Zen);
+        for i in 0..5 {
+            game.ball.position.x = -50.0;
+            game.step(i as f64, 1.0);
+        }
+
+        assert!(game.is_active);
+        assert_eq!((game.score, game.ball.lives), (10, 3));
+    }
+
+    #[test]
+    fn debug_info_shows_what_socd_resolved() {
+        let mut data = InputData::new(0.0);
+        assert_eq!(GameLoop::get_socd_info(&data), "  SOCD: - (axis)\n");
+
+        data.is_left_down = true;
+        data.is_right_down = true;
+        data.is_up_down = true;
+        data.raw_dir = Vector2::new(0.0, -1.0);
+        data.socd_mode = Some(SocdMode::UpPriority);
+        assert_eq!(GameLoop::get_socd_info(&data), format!("  SOCD: {}  L+R -> (0, -1)\n", SocdMode::UpPriority.get_name()));
+    }
+
+    #[test]
+    fn random_angle_is_steeper_where_the_ball_touched_near_an_edge() {
+        let mut game = new_game(InputAction::Down, SCREEN_SIZE.y * 0.5, -0.5);
+        let mut input = InputData::new(0.0);
+        input.raw_dir = Vector2::new(0.0, 1.0);
+
+        // Held down, but the ball keeps heading up off a paddle corner near the top
+        let contact = Vector2::new(30.0, game.ball.radius + 10.0);
+        let angle = game.get_random_angle(&input, contact);
+        assert!(angle <= -game.tunables.paddle_min_angle * 1.5);
+
+        let angle = game.get_random_angle(&input, Vector2::new(30.0, SCREEN_SIZE.y * 0.5));
+        assert!(angle > 0.0);
+    }
+}
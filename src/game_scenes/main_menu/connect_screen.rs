@@ -12,18 +12,18 @@ impl ConnectScreen {
         self.player_txt.centralize();
     }
 
-    fn change_device(self: &mut Self, rl: &RaylibHandle, step: i32) {
-        let mut connected_devices = InputUtils::get_connected_devices(&rl);
-        let devices_amount = connected_devices.len() as i32;
-
-        let mut new_id = self.device_id + step;
-        if new_id < 0 { new_id = devices_amount - 1 }
-        if new_id >= devices_amount { new_id = 0 }
-        self.device_id  = new_id;
-        
-        let device_name = connected_devices[new_id as usize].get_name();
-        self.device_txt.text = device_name;
+    fn change_device(self: &mut Self, step: i32) {
+        let new_id = self.devices.get_next(self.device.as_ref(), step);
+        self.set_device(Some(new_id));
+    }
+
+    fn set_device(self: &mut Self, device: Option<DeviceId>) {
+        self.device_txt.text = match &device {
+            Some(id) => id.get_name(),
+            None => "Device".to_string()
+        };
         self.device_txt.centralize();
+        self.device = device;
     }
 
     pub fn new() -> ConnectScreen {
@@ -31,7 +31,8 @@ impl ConnectScreen {
             title_txt: Text::new("Select Player and Device:", Vector2::new(0.270, 0.25), Color::WHITE, 20),
             connect_btn: Button::new(false, "Connect", Vector2::new(0.7415, 0.475)),
             
-            device_id: -1,
+            devices: DeviceRegistry::new(),
+            device: None,
             player_id: -1,
 
             player_names: vec![
@@ -77,15 +78,22 @@ impl UIScreen for ConnectScreen {
     }
     
     fn update(self: &mut Self, rl: &RaylibHandle) {
-             if self.device_btns[0].is_pressed(rl) { self.change_device(rl, -1) }
-        else if self.device_btns[1].is_pressed(rl) { self.change_device(rl,  1) }
+        // Unplugged gamepads can't stay selected
+        for event in self.devices.update() {
+            if let DeviceEvent::Disconnected(id) = event {
+                if self.device.as_ref() == Some(&id) { self.set_device(None); }
+            }
+        }
+
+             if self.device_btns[0].is_pressed(rl) { self.change_device(-1) }
+        else if self.device_btns[1].is_pressed(rl) { self.change_device( 1) }
 
         else if self.player_btns[0].is_pressed(rl) { self.change_player(rl, -1) }
         else if self.player_btns[1].is_pressed(rl) { self.change_player(rl,  1) }
 
         self.remote_ip_field.update(rl);
         self.connect_btn.enabled =  self.remote_ip_field.is_ipv4() && 
-                                    self.device_id + self.player_id >= 0;
+                                    self.device.is_some() && self.player_id >= 0;
     }
 
     fn get_elements(self: &mut Self, rl: &RaylibHandle) -> ScreenElements {
@@ -101,6 +109,16 @@ impl UIScreen for ConnectScreen {
     }
 
 
+    fn get_focus_order(self: &mut Self) -> Vec<Vec<&mut dyn Focusable>> {
+        return vec![
+            FocusNavigator::row(&mut self.player_btns),
+            FocusNavigator::row(&mut self.device_btns),
+            vec![&mut self.remote_ip_field],
+            vec![&mut self.connect_btn]
+        ];
+    }
+
+    fn get_previous_screen(&self) -> Option<Box<dyn UIScreen>> { Some(Box::new(MultiplayerScreen::new())) }
     fn goes_to_scene(&self) -> bool { false }
     fn is_active(&self) -> bool { self.is_active }
     fn get_next_scene(&self, rl: &RaylibHandle) -> Box<dyn GameScene> {
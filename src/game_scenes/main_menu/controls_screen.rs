@@ -0,0 +1,153 @@
+use super::*;
+
+impl ControlsScreen {
+    fn change_device(self: &mut Self, step: i32) {
+        let new_id = self.devices.get_next(Some(&self.device.get_id()), step);
+        self.device = DeviceRegistry::create_device(&new_id);
+        self.refresh_elements();
+    }
+
+    // Falls back to the keyboard if the gamepad being edited is unplugged
+    fn update_devices(self: &mut Self) {
+        for event in self.devices.update() {
+            let DeviceEvent::Disconnected(id) = event else { continue; };
+            if id != self.device.get_id() { continue; }
+
+            self.device = DeviceRegistry::create_device(&DeviceId::Keyboard(true));
+            self.capturing = None;
+            self.refresh_elements();
+        }
+    }
+
+    // The device's own Back cancels, unless Back is what's being bound
+    fn update_capture(self: &mut Self, action: InputAction) {
+        if action != InputAction::Back && self.device.is_action_pressed(InputAction::Back) {
+            self.capturing = None;
+            self.refresh_elements();
+            return;
+        }
+
+        if let Some(binding) = self.device.capture_binding() {
+            let mut profile = self.device.get_profile();
+            profile.set_binding(action, binding);
+            DeviceRegistry::save_profile(&mut self.device, profile);
+
+            self.capturing = None;
+            self.refresh_elements();
+        }
+    }
+
+    // Buttons are sized by their text, so they're rebuilt when the bindings change
+    fn refresh_elements(self: &mut Self) {
+        let profile = self.device.get_profile();
+        let is_capturing = self.capturing.is_some();
+
+        self.device_txt.text = self.device.get_name();
+        self.device_txt.centralize();
+
+        let cancel: Vec<String> = profile.get_bindings(InputAction::Back).iter().map(|b| b.get_name()).collect();
+        self.title_txt.text = match self.capturing {
+            Some(action) if action == InputAction::Back || cancel.is_empty() => format!("Press a new input for {}", action.get_name()),
+            Some(action) => format!("Press a new input for {} ({} to cancel)", action.get_name(), cancel.join(" / ")),
+            None => "Controls".to_string()
+        };
+        self.title_txt.centralize();
+
+        for (i, action) in InputAction::ALL.iter().enumerate() {
+            let names: Vec<String> = profile.get_bindings(*action).iter().map(|b| b.get_name()).collect();
+            let label = format!("{}: {}", action.get_name(), if names.is_empty() { "-".to_string() } else { names.join(" / ") });
+
+            let is_selected = self.action_btns[i].is_selected;
+            self.action_btns[i] = Button::new(!is_capturing, &label, Vector2::new(0.5, 0.25 + i as f32 * 0.08));
+            self.action_btns[i].is_selected = is_selected;
+        }
+
+        // Disabled buttons can't be focused, so navigation stays put while capturing
+        for button in self.device_btns.iter_mut() { button.enabled = !is_capturing; }
+        self.reset_btn.enabled = !is_capturing;
+        self.settings_btn.enabled = !is_capturing;
+    }
+
+    pub fn new(device: DeviceId) -> ControlsScreen {
+        let mut screen = ControlsScreen {
+            title_txt: Text::new("Controls", Vector2::new(0.5, 0.07), Color::WHITE, 20),
+            device_txt: Text::new("", Vector2::new(0.5, 0.17), Color::GOLD, 20),
+            device_btns: vec![
+                Button::new(true, "<", Vector2::new(0.2, 0.17)),
+                Button::new(true, ">", Vector2::new(0.8, 0.17))
+            ],
+
+            action_btns: InputAction::ALL.iter().map(|a| Button::new(true, a.get_name(), Vector2::new(0.5, 0.5))).collect(),
+            reset_btn: Button::new(true, "Reset to Defaults", Vector2::new(0.5, 0.83)),
+            settings_btn: Button::new(true, "Device Settings", Vector2::new(0.5, 0.92)),
+
+            devices: DeviceRegistry::new(),
+            device: DeviceRegistry::create_device(&device),
+            capturing: None,
+            is_active: true,
+        };
+
+        screen.refresh_elements();
+        return screen;
+    }
+}
+
+impl UIScreen for ControlsScreen {
+    fn update(self: &mut Self, rl: &RaylibHandle) {
+        self.update_devices();
+        if let Some(action) = self.capturing {
+            self.update_capture(action);
+            return;
+        }
+
+             if self.device_btns[0].is_pressed(rl) { self.change_device(-1); }
+        else if self.device_btns[1].is_pressed(rl) { self.change_device( 1); }
+
+        if self.reset_btn.is_pressed(rl) {
+            let profile = self.device.get_default_profile();
+            DeviceRegistry::save_profile(&mut self.device, profile);
+            self.refresh_elements();
+        }
+
+        if self.settings_btn.is_pressed(rl) {
+            self.is_active = false;
+            return;
+        }
+
+        if let Some(i) = self.action_btns.iter().position(|b| b.is_pressed(rl)) {
+            self.capturing = Some(InputAction::ALL[i]);
+            self.refresh_elements();
+        }
+    }
+
+    fn get_elements(self: &mut Self, rl: &RaylibHandle) -> ScreenElements {
+        let mut buttons: Vec<Button> = vec![self.reset_btn.clone(), self.settings_btn.clone()];
+        buttons.append(&mut self.device_btns.clone());
+        buttons.append(&mut self.action_btns.clone());
+
+        return ScreenElements::new(rl, vec![self.title_txt.clone(), self.device_txt.clone()], buttons, vec![]);
+    }
+
+    fn get_focus_order(self: &mut Self) -> Vec<Vec<&mut dyn Focusable>> {
+        let mut rows = vec![FocusNavigator::row(&mut self.device_btns)];
+        rows.extend(self.action_btns.iter_mut().map(|b| vec![b as &mut dyn Focusable]));
+        rows.push(vec![&mut self.reset_btn]);
+        rows.push(vec![&mut self.settings_btn]);
+        return rows;
+    }
+
+    // Back cancels the capture instead of leaving the screen
+    fn get_previous_screen(&self) -> Option<Box<dyn UIScreen>> {
+        if self.capturing.is_some() { return None; }
+        return Some(Box::new(TitleScreen::new()));
+    }
+
+    fn goes_to_scene(&self) -> bool { false }
+    fn is_active(&self) -> bool { self.is_active }
+    fn get_next_screen(&self, rl: &RaylibHandle) -> Box<dyn UIScreen> {
+        return Box::new(DeviceSettingsScreen::new(self.device.get_id()));
+    }
+    fn get_next_scene(&self, rl: &RaylibHandle) -> Box<dyn GameScene> {
+        panic!("This screen doesn't lead to a scene, should've called 'get_next_screen' instead.");
+    }
+}
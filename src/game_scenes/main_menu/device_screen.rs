@@ -2,32 +2,94 @@ use super::*;
 
 impl DeviceScreen {
     fn update_start_btn(self: &mut Self) {
-        if self.selected_gamemode == GameMode::Singleplayer {
-            self.start_btn.enabled = self.selected_devices[0] >= 0;
+        if self.selected_gamemode != GameMode::Multiplayer {
+            self.start_btn.enabled = self.selected_devices[0].is_some();
         }
         else {
-            self.start_btn.enabled = self.selected_devices[0] >= 0 && self.selected_devices[1] >= 0;
+            self.start_btn.enabled = self.selected_devices[0].is_some() && self.selected_devices[1].is_some();
         }
     }
 
-    fn change_device(self: &mut Self, rl: &RaylibHandle, player_id: usize, step: i32) {
-        let mut connected_devices = InputUtils::get_connected_devices(&rl);
-        let devices_amount = connected_devices.len() as i32;
-
-        let mut new_id = self.selected_devices[player_id] + step;
-        if new_id < 0 { new_id = devices_amount - 1 }
-        if new_id >= devices_amount { new_id = 0 }
-        self.selected_devices[player_id] = new_id;
+    fn change_device(self: &mut Self, player_id: usize, step: i32) {
+        let new_id = self.devices.get_next(self.selected_devices[player_id].as_ref(), step);
+        self.set_device(player_id, Some(new_id));
 
         // Make sure the device wasn't selected 
         if self.selected_devices[0] == self.selected_devices[1] {
             let other_player = i32::abs(player_id as i32 - 1) as usize;
-            self.change_device(rl, other_player, step * -1);
+            self.change_device(other_player, step * -1);
         }
-        
+    }
+
+    fn set_device(self: &mut Self, player_id: usize, device: Option<DeviceId>) {
         let text = if player_id == 0 { &mut self.device_1_txt } else { &mut self.device_2_txt };
-        text.text = connected_devices[new_id as usize].get_name();
+        text.text = match &device {
+            Some(id) => id.get_name(),
+            None => format!("Player {}", player_id + 1)
+        };
+        text.centralize();
+        self.selected_devices[player_id] = device;
+        self.refresh_mode(player_id);
+    }
+
+    // Only gamepads have a mode to choose, it's saved with the rest of their profile
+    fn change_mode(self: &mut Self, player_id: usize, step: i32) {
+        let Some(id) = self.selected_devices[player_id].clone() else { return; };
+        let mut device = DeviceRegistry::create_device(&id);
+        let mut profile = device.get_profile();
+
+        let modes = GamepadMode::ALL;
+        let index = modes.iter().position(|m| *m == profile.gamepad_mode).unwrap_or(0) as i32;
+        profile.gamepad_mode = modes[(index + step).rem_euclid(modes.len() as i32) as usize];
+
+        DeviceRegistry::save_profile(&mut device, profile);
+        self.refresh_mode(player_id);
+    }
+
+    fn refresh_mode(self: &mut Self, player_id: usize) {
+        let mode = match &self.selected_devices[player_id] {
+            Some(id @ DeviceId::Gamepad(_, _)) => Some(DeviceRegistry::create_device(id).get_profile().gamepad_mode),
+            _ => None
+        };
+
+        let (text, buttons) = if player_id == 0 { (&mut self.mode_1_txt, &mut self.mode_1_btns) } 
+                              else { (&mut self.mode_2_txt, &mut self.mode_2_btns) };
+        text.text = match mode {
+            Some(mode) => format!("Mode: {}", mode.get_name()),
+            None => String::new()
+        };
         text.centralize();
+        for button in buttons.iter_mut() { button.enabled = mode.is_some(); }
+    }
+
+    fn change_bounce_model(self: &mut Self, step: i32) {
+        let models = BounceModel::ALL;
+        let index = models.iter().position(|m| *m == self.rules.bounce_model).unwrap_or(0) as i32;
+        self.rules.bounce_model = models[(index + step).rem_euclid(models.len() as i32) as usize];
+
+        self.bounce_txt.text = format!("Bounces: {}", self.rules.bounce_model.get_name());
+        self.bounce_txt.centralize();
+    }
+
+    fn update_devices(self: &mut Self) {
+        let players_amount = if self.selected_gamemode == GameMode::Multiplayer { 2 } else { 1 };
+
+        for event in self.devices.update() {
+            match event {
+                // Newly plugged gamepads go to the first player without a device
+                DeviceEvent::Connected(id) => {
+                    if let Some(player_id) = (0..players_amount).find(|p| self.selected_devices[*p].is_none()) {
+                        self.set_device(player_id, Some(id));
+                    }
+                },
+                // Unplugged gamepads can't stay selected
+                DeviceEvent::Disconnected(id) => {
+                    for player_id in 0..players_amount {
+                        if self.selected_devices[player_id].as_ref() == Some(&id) { self.set_device(player_id, None); }
+                    }
+                }
+            }
+        }
     }
 
     pub fn new(mode: GameMode) -> DeviceScreen {
@@ -44,21 +106,42 @@ impl DeviceScreen {
 
         return DeviceScreen {
             title_txt: Text::new("Select Players Input:", Vector2::new(0.5, 0.25), Color::WHITE, 20),
-            device_1_txt: Text::new("Player 1", Vector2::new(0.5, 0.4), device_txt_colors[0], 20),
-            device_2_txt: Text::new("Player 2", Vector2::new(0.5, 0.5), device_txt_colors[1], 20),
+            device_1_txt: Text::new("Player 1", Vector2::new(0.5, 0.35), device_txt_colors[0], 20),
+            device_2_txt: Text::new("Player 2", Vector2::new(0.5, 0.55), device_txt_colors[1], 20),
+            mode_1_txt: Text::new("", Vector2::new(0.5, 0.44), Color::GRAY, 16),
+            mode_2_txt: Text::new("", Vector2::new(0.5, 0.64), Color::GRAY, 16),
 
             device_1_btns: vec![
-                Button::new(true, "<", Vector2::new(0.3, 0.4)),
-                Button::new(true, ">", Vector2::new(0.7, 0.4))
+                Button::new(true, "<", Vector2::new(0.3, 0.35)),
+                Button::new(true, ">", Vector2::new(0.7, 0.35))
             ],
 
             device_2_btns: vec![
-                Button::new(!is_singleplayer, "<", Vector2::new(0.3, 0.5)),
-                Button::new(!is_singleplayer, ">", Vector2::new(0.7, 0.5))
+                Button::new(!is_singleplayer, "<", Vector2::new(0.3, 0.55)),
+                Button::new(!is_singleplayer, ">", Vector2::new(0.7, 0.55))
+            ],
+
+            // Enabled once a gamepad is selected
+            mode_1_btns: vec![
+                Button::new(false, "<", Vector2::new(0.3, 0.44)),
+                Button::new(false, ">", Vector2::new(0.7, 0.44))
+            ],
+
+            mode_2_btns: vec![
+                Button::new(false, "<", Vector2::new(0.3, 0.64)),
+                Button::new(false, ">", Vector2::new(0.7, 0.64))
             ],
             
-            selected_devices: vec![-1, -1],
-            start_btn: Button::new(false, "Start", Vector2::new(0.5, 0.75)),
+            devices: DeviceRegistry::new(),
+            selected_devices: vec![None, None],
+            start_btn: Button::new(false, "Next", Vector2::new(0.5, 0.86)),
+
+            rules: GameRules::new(),
+            bounce_txt: Text::new(&format!("Bounces: {}", BounceModel::Random.get_name()), Vector2::new(0.5, 0.75), Color::WHITE, 16),
+            bounce_btns: vec![
+                Button::new(true, "<", Vector2::new(0.3, 0.75)),
+                Button::new(true, ">", Vector2::new(0.7, 0.75))
+            ],
 
             is_active: true,
             selected_gamemode: mode,
@@ -67,21 +150,35 @@ impl DeviceScreen {
 }
 
 impl UIScreen for DeviceScreen {
-    fn get_next_scene(&self, rl: &RaylibHandle) -> Box<dyn GameScene> {
-        let devices = (InputUtils::get_device_by_id(self.selected_devices[0]),
-                       InputUtils::get_device_by_id(self.selected_devices[1]));
+    fn get_next_screen(&self, rl: &RaylibHandle) -> Box<dyn UIScreen> {
+        // Player 2 isn't controlled in singleplayer, so it just mirrors player 1
+        let player_1 = self.selected_devices[0].clone().expect("Player 1 didn't select a device");
+        let player_2 = self.selected_devices[1].clone().unwrap_or(player_1.clone());
 
-        return Box::new(GameLoop::new(self.selected_gamemode, devices));
+        if self.selected_gamemode == GameMode::Multiplayer {
+            return Box::new(VersusScreen::new(self.rules.clone(), (player_1, player_2)));
+        }
+        return Box::new(StageScreen::new(self.selected_gamemode, self.rules.clone(), (player_1, player_2)));
     }
 
     fn update(self: &mut Self, rl: &RaylibHandle) {
+        self.update_devices();
         if self.start_btn.is_pressed(rl) { self.is_active = false; }
 
-             if self.device_1_btns[0].is_pressed(&rl) { self.change_device(rl, 0, -1); }
-        else if self.device_1_btns[1].is_pressed(&rl) { self.change_device(rl, 0,  1); }
+             if self.device_1_btns[0].is_pressed(&rl) { self.change_device(0, -1); }
+        else if self.device_1_btns[1].is_pressed(&rl) { self.change_device(0,  1); }
+
+             if self.device_2_btns[0].is_pressed(&rl) { self.change_device(1, -1); }
+        else if self.device_2_btns[1].is_pressed(&rl) { self.change_device(1,  1); }
 
-             if self.device_2_btns[0].is_pressed(&rl) { self.change_device(rl, 1, -1); }
-        else if self.device_2_btns[1].is_pressed(&rl) { self.change_device(rl, 1,  1); }
+             if self.mode_1_btns[0].is_pressed(rl) { self.change_mode(0, -1); }
+        else if self.mode_1_btns[1].is_pressed(rl) { self.change_mode(0,  1); }
+
+             if self.mode_2_btns[0].is_pressed(rl) { self.change_mode(1, -1); }
+        else if self.mode_2_btns[1].is_pressed(rl) { self.change_mode(1,  1); }
+
+             if self.bounce_btns[0].is_pressed(rl) { self.change_bounce_model(-1); }
+        else if self.bounce_btns[1].is_pressed(rl) { self.change_bounce_model( 1); }
 
         self.update_start_btn();
     }
@@ -90,16 +187,38 @@ impl UIScreen for DeviceScreen {
         let mut buttons: Vec<Button> = vec![self.start_btn.clone()];
         buttons.append(&mut self.device_1_btns.clone());
         buttons.append(&mut self.device_2_btns.clone());
+        buttons.append(&mut self.mode_1_btns.clone());
+        buttons.append(&mut self.mode_2_btns.clone());
+        buttons.append(&mut self.bounce_btns.clone());
 
         return ScreenElements::new(rl, 
-            vec![self.title_txt.clone(), self.device_1_txt.clone(), self.device_2_txt.clone()], 
+            vec![self.title_txt.clone(), self.device_1_txt.clone(), self.device_2_txt.clone(),
+                 self.mode_1_txt.clone(), self.mode_2_txt.clone(), self.bounce_txt.clone()], 
             buttons, vec![]
         )
     }
     
-    fn goes_to_scene(&self) -> bool { true }
+    fn get_focus_order(self: &mut Self) -> Vec<Vec<&mut dyn Focusable>> {
+        return vec![
+            FocusNavigator::row(&mut self.device_1_btns),
+            FocusNavigator::row(&mut self.mode_1_btns),
+            FocusNavigator::row(&mut self.device_2_btns),
+            FocusNavigator::row(&mut self.mode_2_btns),
+            FocusNavigator::row(&mut self.bounce_btns),
+            vec![&mut self.start_btn]
+        ];
+    }
+
+    fn get_previous_screen(&self) -> Option<Box<dyn UIScreen>> {
+        if self.selected_gamemode == GameMode::Multiplayer { 
+            return Some(Box::new(MultiplayerScreen::new())); 
+        }
+        return Some(Box::new(TitleScreen::new()));
+    }
+
+    fn goes_to_scene(&self) -> bool { false }
     fn is_active(&self) -> bool { self.is_active }
-    fn get_next_screen(&self, rl: &RaylibHandle) -> Box<dyn UIScreen> {
-        panic!("There's no screen after this one, should've called 'get_next_scene' instead.");
+    fn get_next_scene(&self, rl: &RaylibHandle) -> Box<dyn GameScene> {
+        panic!("This screen doesn't lead to a scene, should've called 'get_next_screen' instead.");
     }
 }
\ No newline at end of file
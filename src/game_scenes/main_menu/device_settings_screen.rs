@@ -0,0 +1,214 @@
+use super::*;
+
+const SETTING_NAMES: [&str; 8] = ["Stick", "Deadzone", "Inner Deadzone", "Outer Deadzone",
+                                  "Response Curve", "Curve Exponent", "Snapness", "SOCD"];
+const VISUALISER_POS: Vector2 = Vector2 { x: 0.8, y: 0.45 };
+const VISUALISER_RADIUS: f32 = 90.0;
+
+impl DeviceSettingsScreen {
+    fn is_gamepad(self: &Self) -> bool {
+        return matches!(self.device.get_id(), DeviceId::Gamepad(_, _));
+    }
+
+    // The mouse drag goes through the same deadzones and curve as a stick
+    fn is_analog(self: &Self) -> bool {
+        return self.is_gamepad() || self.device.get_id() == DeviceId::Mouse;
+    }
+
+    fn change_setting(self: &mut Self, setting: usize, step: i32) {
+        let mut profile = self.device.get_profile();
+        let analog = &mut profile.analog;
+        let step_by = |value: f32, amount: f32, min: f32, max: f32| -> f32 {
+            let stepped = ((value + amount * step as f32) / amount).round() * amount;
+            return stepped.clamp(min, max);
+        };
+
+        match setting {
+            0 => {
+                let sticks = [AnalogStick::Left, AnalogStick::Right, AnalogStick::DPad];
+                let index = sticks.iter().position(|s| *s == analog.stick).unwrap_or(0) as i32;
+                analog.stick = sticks[(index + step).rem_euclid(sticks.len() as i32) as usize];
+            },
+            1 => {
+                analog.deadzone_shape = match analog.deadzone_shape {
+                    DeadzoneShape::Radial => DeadzoneShape::Axial,
+                    DeadzoneShape::Axial => DeadzoneShape::Radial,
+                };
+            },
+            2 => analog.inner_deadzone = step_by(analog.inner_deadzone, 0.05, 0.0, 0.5),
+            3 => analog.outer_deadzone = step_by(analog.outer_deadzone, 0.05, 0.5, 1.0),
+            4 => {
+                let curves = [ResponseCurve::Linear, ResponseCurve::Quadratic, ResponseCurve::Custom(1.5)];
+                let index = curves.iter().position(|c| c.get_name() == analog.curve.get_name()).unwrap_or(0) as i32;
+                analog.curve = curves[(index + step).rem_euclid(curves.len() as i32) as usize];
+            },
+            5 => {
+                if let ResponseCurve::Custom(exponent) = analog.curve {
+                    analog.curve = ResponseCurve::Custom(step_by(exponent, 0.25, 0.25, 4.0));
+                }
+            },
+            // 0 means the player's default smoothing
+            6 => {
+                let snapness = (profile.snapness.unwrap_or(0.0) as i32 + step).rem_euclid(21);
+                profile.snapness = if snapness == 0 { None } else { Some(snapness as f32) };
+            },
+            // None means the player's default mode
+            _ => {
+                let modes: Vec<Option<SocdMode>> = [None].into_iter().chain(SocdMode::ALL.map(Some)).collect();
+                let index = modes.iter().position(|m| *m == profile.socd_mode).unwrap_or(0) as i32;
+                profile.socd_mode = modes[(index + step).rem_euclid(modes.len() as i32) as usize];
+            }
+        }
+
+        DeviceRegistry::save_profile(&mut self.device, profile);
+        self.refresh_elements();
+    }
+
+    fn refresh_elements(self: &mut Self) {
+        let profile = self.device.get_profile();
+        let analog = profile.analog;
+        let is_custom_curve = matches!(analog.curve, ResponseCurve::Custom(_));
+
+        let values = [
+            match analog.stick {
+                AnalogStick::Left => "Left".to_string(),
+                AnalogStick::Right => "Right".to_string(),
+                AnalogStick::DPad => "D-Pad".to_string(),
+            },
+            match analog.deadzone_shape {
+                DeadzoneShape::Radial => "Radial".to_string(),
+                DeadzoneShape::Axial => "Axial".to_string(),
+            },
+            format!("{:.2}", analog.inner_deadzone),
+            format!("{:.2}", analog.outer_deadzone),
+            analog.curve.get_name().to_string(),
+            match analog.curve {
+                ResponseCurve::Custom(exponent) => format!("{:.2}", exponent),
+                _ => "-".to_string()
+            },
+            profile.snapness.map_or("Default".to_string(), |s| format!("{}", s)),
+            profile.socd_mode.map_or("Default", |m| m.get_name()).to_string(),
+        ];
+
+        // Keyboards only have the snapness and SOCD to change, and only gamepads have sticks
+        let is_gamepad = self.is_gamepad();
+        let is_analog = self.is_analog();
+        for (i, value) in values.iter().enumerate() {
+            let enabled = match i {
+                0 => is_gamepad,
+                5 => is_analog && is_custom_curve,
+                6 | 7 => true,
+                _ => is_analog
+            };
+
+            self.value_txts[i].text = value.clone();
+            self.value_txts[i].color = if enabled { Color::GOLD } else { ScreenElements::DISABLED_COLOR };
+            self.value_txts[i].centralize();
+            for button in self.setting_btns[i].iter_mut() { button.enabled = enabled; }
+        }
+    }
+
+    // Stick range, deadzones, and where the raw and processed stick positions are
+    fn get_visualiser(self: &Self) -> Vec<Shape> {
+        let analog = self.device.get_profile().analog;
+        let center = Vector2::new(SCREEN_SIZE.x * VISUALISER_POS.x, SCREEN_SIZE.y * VISUALISER_POS.y);
+        let inner = analog.inner_deadzone * VISUALISER_RADIUS;
+        let deadzone_color = Color::new(255, 40, 0, 130);
+
+        let mut shapes = vec![
+            Shape::CircleLines(center, VISUALISER_RADIUS, Color::GRAY),
+            Shape::CircleLines(center, analog.outer_deadzone * VISUALISER_RADIUS, Color::DARKGRAY),
+        ];
+
+        match analog.deadzone_shape {
+            DeadzoneShape::Radial => shapes.push(Shape::CircleLines(center, inner, deadzone_color)),
+            DeadzoneShape::Axial => {
+                shapes.push(Shape::RectangleLines(Rectangle::new(center.x - inner, center.y - VISUALISER_RADIUS,
+                                                                 inner * 2.0, VISUALISER_RADIUS * 2.0), deadzone_color));
+                shapes.push(Shape::RectangleLines(Rectangle::new(center.x - VISUALISER_RADIUS, center.y - inner,
+                                                                 VISUALISER_RADIUS * 2.0, inner * 2.0), deadzone_color));
+            }
+        }
+
+        let processed = analog.apply(self.raw_axis);
+        shapes.push(Shape::Line(center, center + processed * VISUALISER_RADIUS, Color::GOLD));
+        shapes.push(Shape::Circle(center + self.raw_axis * VISUALISER_RADIUS, 4.0, Color::GRAY));
+        shapes.push(Shape::Circle(center + processed * VISUALISER_RADIUS, 6.0, Color::GOLD));
+        return shapes;
+    }
+
+    pub fn new(device: DeviceId) -> DeviceSettingsScreen {
+        let row_pos = |i: usize| 0.17 + i as f32 * 0.08;
+
+        let mut screen = DeviceSettingsScreen {
+            title_txt: Text::new(&format!("{} Settings", device.get_name()), Vector2::new(0.5, 0.07), Color::WHITE, 20),
+            legend_txt: Text::new("Gray: raw   Gold: output", Vector2::new(VISUALISER_POS.x, 0.7), Color::GRAY, 14),
+            label_txts: SETTING_NAMES.iter().enumerate().map(|(i, name)| {
+                Text::new(name, Vector2::new(0.15, row_pos(i)), Color::WHITE, 18)
+            }).collect(),
+            value_txts: (0..SETTING_NAMES.len()).map(|i| Text::new("", Vector2::new(0.45, row_pos(i)), Color::GOLD, 18)).collect(),
+            setting_btns: (0..SETTING_NAMES.len()).map(|i| vec![
+                Button::new(true, "<", Vector2::new(0.33, row_pos(i))),
+                Button::new(true, ">", Vector2::new(0.57, row_pos(i)))
+            ]).collect(),
+            back_btn: Button::new(true, "Back", Vector2::new(0.5, 0.88)),
+
+            device: DeviceRegistry::create_device(&device),
+            raw_axis: Vector2::zero(),
+            is_active: true,
+        };
+
+        screen.refresh_elements();
+        return screen;
+    }
+}
+
+impl UIScreen for DeviceSettingsScreen {
+    fn update(self: &mut Self, rl: &RaylibHandle) {
+        if self.is_analog() {
+            self.device.poll(rl.get_time());
+            self.raw_axis = self.device.get_raw_axis();
+        }
+        if self.back_btn.is_pressed(rl) { self.is_active = false; }
+
+        for i in 0..SETTING_NAMES.len() {
+                 if self.setting_btns[i][0].is_pressed(rl) { self.change_setting(i, -1); }
+            else if self.setting_btns[i][1].is_pressed(rl) { self.change_setting(i,  1); }
+        }
+    }
+
+    fn get_elements(self: &mut Self, rl: &RaylibHandle) -> ScreenElements {
+        let mut texts = vec![self.title_txt.clone()];
+        texts.append(&mut self.label_txts.clone());
+        texts.append(&mut self.value_txts.clone());
+
+        let mut buttons = vec![self.back_btn.clone()];
+        for row in &self.setting_btns { buttons.append(&mut row.clone()); }
+
+        let mut elements = ScreenElements::new(rl, texts, buttons, vec![]);
+        if self.is_analog() {
+            elements.texts.push(self.legend_txt.clone());
+            elements.shapes = self.get_visualiser();
+        }
+        return elements;
+    }
+
+    fn get_focus_order(self: &mut Self) -> Vec<Vec<&mut dyn Focusable>> {
+        let mut rows: Vec<Vec<&mut dyn Focusable>> = self.setting_btns.iter_mut().map(|r| FocusNavigator::row(r)).collect();
+        rows.push(vec![&mut self.back_btn]);
+        return rows;
+    }
+
+    fn get_previous_screen(&self) -> Option<Box<dyn UIScreen>> {
+        return Some(Box::new(ControlsScreen::new(self.device.get_id())));
+    }
+
+    fn goes_to_scene(&self) -> bool { false }
+    fn is_active(&self) -> bool { self.is_active }
+    fn get_next_screen(&self, rl: &RaylibHandle) -> Box<dyn UIScreen> {
+        return Box::new(ControlsScreen::new(self.device.get_id()));
+    }
+    fn get_next_scene(&self, rl: &RaylibHandle) -> Box<dyn GameScene> {
+        panic!("This screen doesn't lead to a scene, should've called 'get_next_screen' instead.");
+    }
+}
@@ -2,20 +2,33 @@ mod title_screen;
 mod device_screen;
 mod connect_screen;
 mod multiplayer_screen;
+mod controls_screen;
+mod device_settings_screen;
+mod stage_screen;
+mod power_ups_screen;
+mod versus_screen;
+mod options_screen;
 
 use super::*;
 use crate::utils::*;
+use crate::save_system::*;
+use crate::input_system::*;
+use crate::settings_system::*;
 use regex::Regex;
 
-pub enum MenuScreen { TitleScreen, DeviceScreen, ConnectScreen, MultiplayerScreen }
+pub enum MenuScreen { TitleScreen, DeviceScreen, ConnectScreen, MultiplayerScreen, ControlsScreen, PowerUpsScreen, OptionsScreen }
 
 struct TitleScreen {
     title_txt: Text,
     hiscore_txt: Text,
     selected_mode: GameMode,
+    solo_mode: GameMode,    // Shown on the singleplayer button
 
     singleplayer_btn: Button,  
+    solo_mode_btns: Vec<Button>,
     multiplayer_btn: Button,
+    controls_btn: Button,
+    options_btn: Button,
     quit_btn: Button,
 
     is_active: bool,
@@ -34,16 +47,116 @@ struct DeviceScreen {
     title_txt: Text,
     device_1_txt: Text,
     device_2_txt: Text,
+    mode_1_txt: Text,
+    mode_2_txt: Text,
 
-    selected_devices: Vec<i32>,
+    devices: DeviceRegistry,
+    selected_devices: Vec<Option<DeviceId>>,
     device_1_btns: Vec<Button>,
     device_2_btns: Vec<Button>,
+    mode_1_btns: Vec<Button>,
+    mode_2_btns: Vec<Button>,
     start_btn: Button,
 
+    rules: GameRules,
+    bounce_txt: Text,
+    bounce_btns: Vec<Button>,
+
+    is_active: bool,
+    selected_gamemode: GameMode
+}
+
+struct StageScreen {
+    title_txt: Text,
+    stage_txt: Text,
+    stage_btns: Vec<Button>,
+    power_ups_btn: Button,
+    start_btn: Button,
+
+    stages: Vec<Stage>,
+    selected_stage: usize,
+    preview_time: f32,  // Keeps moving obstacles going in the preview
+
+    rules: GameRules,
+    devices: (DeviceId, DeviceId),
     is_active: bool,
+    next_screen: Option<MenuScreen>,    // Straight to the game if there's none
     selected_gamemode: GameMode
 }
 
+// Scoring for local multiplayer, picked before the stage
+struct VersusScreen {
+    title_txt: Text,
+    label_txts: Vec<Text>,
+    value_txts: Vec<Text>,
+    setting_btns: Vec<Vec<Button>>, // '<' and '>' for each setting
+    next_btn: Button,
+
+    is_versus: bool,
+    match_rules: MatchRules,    // Kept while switching to classic and back
+    rules: GameRules,
+    devices: (DeviceId, DeviceId),
+    is_active: bool,
+}
+
+// Which power-ups can spawn, saved for each game mode
+struct PowerUpsScreen {
+    title_txt: Text,
+    label_txts: Vec<Text>,
+    toggle_btns: Vec<Button>,
+    back_btn: Button,
+
+    enabled: Vec<PowerUp>,
+    rules: GameRules,   // Kept to go back to the stage select as it was
+    devices: (DeviceId, DeviceId),
+    is_active: bool,
+    selected_gamemode: GameMode
+}
+
+// Settings for the whole game, also opened from the pause menu
+pub struct OptionsScreen {
+    title_txt: Text,
+    category_txt: Text,
+    category_btns: Vec<Button>,
+    label_txts: Vec<Text>,
+    value_txts: Vec<Text>,
+    setting_btns: Vec<Vec<Button>>, // '<' and '>' for each row, unused ones are hidden
+    back_btn: Button,
+
+    settings: Settings,
+    revision: u32,  // Of the settings shown, the window saves its own changes too
+    category: usize,
+    is_active: bool,
+}
+
+struct ControlsScreen {
+    title_txt: Text,
+    device_txt: Text,
+    device_btns: Vec<Button>,
+    action_btns: Vec<Button>,
+    reset_btn: Button,
+    settings_btn: Button,
+
+    devices: DeviceRegistry,
+    device: Box<dyn InputDevice>,
+    capturing: Option<InputAction>, // Waiting for a new input for this action
+
+    is_active: bool,
+}
+
+struct DeviceSettingsScreen {
+    title_txt: Text,
+    legend_txt: Text,
+    label_txts: Vec<Text>,
+    value_txts: Vec<Text>,
+    setting_btns: Vec<Vec<Button>>, // '<' and '>' for each setting
+    back_btn: Button,
+
+    device: Box<dyn InputDevice>,
+    raw_axis: Vector2,
+    is_active: bool,
+}
+
 struct ConnectScreen {
     title_txt: Text,
     connect_btn: Button,
@@ -54,7 +167,8 @@ struct ConnectScreen {
     player_names:Vec<String>,
     player_colors: Vec<Color>,
 
-    device_id: i32,
+    devices: DeviceRegistry,
+    device: Option<DeviceId>,
     device_txt: Text,
 
     device_btns: Vec<Button>,
@@ -79,32 +193,32 @@ impl GameScene for MainMenu {
                 return;
             }
             self.current_screen = self.current_screen.get_next_screen(&rl);  
+            self.focus.reset();
         }
 
-        self.current_screen.update(&rl);
-    }
-
-    fn draw(&mut self, rl: &mut RaylibHandle, thread: &RaylibThread) {        
-        let elements = self.current_screen.get_elements(rl);
-        let mut draw_handle = rl.begin_drawing(thread);
-        draw_handle.clear_background(Color::BLACK);
-        
-        for text in &elements.texts {
-            draw_handle.draw_text(&text.text, text.pos.x as i32, text.pos.y as i32, 
-                text.size, text.color);
+        // Devices are created again when one is plugged in or their bindings are saved
+        if !self.devices.update().is_empty() || ProfileData::get_revision() != self.profiles_revision {
+            self.refresh_devices();
         }
 
-        for (button, color) in &elements.buttons {
-            draw_handle.draw_text(&button.text, button.pos.x as i32, button.pos.y as i32,
-                20 as i32, color);
+        // Go back to the previous screen
+        let mut devices: Vec<&mut dyn InputDevice> = self.menu_devices.iter_mut().map(|d| d.as_mut() as &mut dyn InputDevice).collect();
+        let command = self.focus.update(rl, self.current_screen.get_focus_order(), &mut devices);
+        if command == NavCommand::Back {
+            if let Some(previous_screen) = self.current_screen.get_previous_screen() {
+                self.current_screen = previous_screen;
+                self.focus.reset();
+                return;
+            }
         }
 
-        for field in &elements.fields {
-            draw_handle.draw_rectangle_rec(field.rects[0], field.colors[0]);
-            draw_handle.draw_rectangle_rec(field.rects[1], field.colors[1]);
-            draw_handle.draw_text(&field.text.text, field.text.pos.x as i32, field.text.pos.y as i32, 
-                field.text.size, field.text.color);
-        }
+        self.current_screen.update(&rl);
+    }
+
+    fn draw(&mut self, canvas: &mut Canvas) {        
+        let elements = self.current_screen.get_elements(canvas);
+        canvas.clear_background(Color::BLACK);
+        elements.draw(canvas);
     }
 
     fn is_active(&self) -> bool { return self.is_active; }
@@ -114,10 +228,22 @@ impl GameScene for MainMenu {
 }
 
 impl MainMenu { 
+    fn refresh_devices(self: &mut Self) {
+        self.profiles_revision = ProfileData::get_revision();
+        self.menu_devices = self.devices.create_devices();
+    }
+
     pub fn new() -> MainMenu {
-        return MainMenu {
+        let mut menu = MainMenu {
             current_screen: Box::new(TitleScreen::new()),
-            is_active: true
-        }            
+            focus: FocusNavigator::new(),
+            is_active: true,
+            devices: DeviceRegistry::new(),
+            menu_devices: Vec::new(),
+            profiles_revision: 0,
+        };
+
+        menu.refresh_devices();
+        return menu;
     }
 }
\ No newline at end of file
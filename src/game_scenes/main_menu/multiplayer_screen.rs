@@ -38,6 +38,14 @@ impl UIScreen for MultiplayerScreen {
         );
     }
 
+    fn get_focus_order(self: &mut Self) -> Vec<Vec<&mut dyn Focusable>> {
+        return vec![
+            vec![&mut self.local_multiplayer as &mut dyn Focusable],
+            vec![&mut self.online_multiplayer]
+        ];
+    }
+
+    fn get_previous_screen(&self) -> Option<Box<dyn UIScreen>> { Some(Box::new(TitleScreen::new())) }
     fn goes_to_scene(&self) -> bool { false }
     fn is_active(&self) -> bool { self.is_active }
     fn get_next_scene(&self, rl: &RaylibHandle) -> Box<dyn GameScene> {
@@ -0,0 +1,181 @@
+use super::*;
+
+const CATEGORIES: [&str; 4] = ["Video", "Audio", "Input", "Network"];
+const SETTING_NAMES: [&[&str]; 4] = [
+    &["Resolution", "Fullscreen", "VSync", "FPS Limit"],
+    &["Master Volume", "Music Volume", "Effects Volume"],
+    &["P1 Smoothing", "P2 Smoothing", "P1 SOCD", "P2 SOCD"],
+    &["Port"],
+];
+const MAX_ROWS: usize = 4;
+
+impl OptionsScreen {
+    fn change_category(self: &mut Self, step: i32) {
+        self.category = (self.category as i32 + step).rem_euclid(CATEGORIES.len() as i32) as usize;
+        self.refresh_elements();
+    }
+
+    // Saved right away, the window and audio pick the changes up from there.
+    // Loaded again first, so nothing saved since the screen opened is written back over
+    fn change_setting(self: &mut Self, row: usize, step: i32) {
+        self.settings = Settings::load();
+        let cycle = |index: usize, amount: usize| (index as i32 + step).rem_euclid(amount as i32) as usize;
+        let video = &mut self.settings.video;
+        let audio = &mut self.settings.audio;
+        let input = &mut self.settings.input;
+
+        match (self.category, row) {
+            (0, 0) => {
+                let index = RESOLUTIONS.iter().position(|r| *r == video.resolution).unwrap_or(0);
+                video.resolution = RESOLUTIONS[cycle(index, RESOLUTIONS.len())];
+            },
+            (0, 1) => video.fullscreen = !video.fullscreen,
+            (0, 2) => video.vsync = !video.vsync,
+            (0, _) => {
+                let index = FPS_LIMITS.iter().position(|f| *f == video.fps_limit).unwrap_or(0);
+                video.fps_limit = FPS_LIMITS[cycle(index, FPS_LIMITS.len())];
+            },
+            (1, _) => {
+                let volume = match row { 0 => &mut audio.master_volume, 1 => &mut audio.music_volume, _ => &mut audio.effects_volume };
+                *volume = ((*volume * 10.0).round() + step as f32).clamp(0.0, 10.0) / 10.0;
+            },
+            (2, 0 | 1) => input.snapness[row] = (input.snapness[row] + step as f32).clamp(1.0, 20.0),
+            (2, _) => {
+                let player = row - 2;
+                let index = SocdMode::ALL.iter().position(|m| *m == input.socd_modes[player]).unwrap_or(0);
+                input.socd_modes[player] = SocdMode::ALL[cycle(index, SocdMode::ALL.len())];
+            },
+            _ => self.settings.network.port = (self.settings.network.port as i32 + step).clamp(1024, u16::MAX as i32) as u16,
+        }
+
+        self.settings.save();
+        self.revision = Settings::get_revision();
+        self.refresh_elements();
+    }
+
+    // Fullscreen and window size can change from outside the screen
+    fn check_revision(self: &mut Self) {
+        if Settings::get_revision() == self.revision { return; }
+        self.settings = Settings::load();
+        self.revision = Settings::get_revision();
+        self.refresh_elements();
+    }
+
+    fn get_values(self: &Self) -> Vec<String> {
+        let on_off = |value: bool| if value { "On".to_string() } else { "Off".to_string() };
+        let percent = |volume: f32| format!("{:.0}%", volume * 100.0);
+        let (video, audio, input) = (self.settings.video, self.settings.audio, self.settings.input);
+
+        match self.category {
+            0 => return vec![
+                format!("{}x{}", video.resolution.0, video.resolution.1),
+                on_off(video.fullscreen),
+                on_off(video.vsync),
+                if video.fps_limit == 0 { "Unlimited".to_string() } else { format!("{}", video.fps_limit) },
+            ],
+            1 => return vec![percent(audio.master_volume), percent(audio.music_volume), percent(audio.effects_volume)],
+            2 => return vec![
+                format!("{}", input.snapness[0]),
+                format!("{}", input.snapness[1]),
+                input.socd_modes[0].get_name().to_string(),
+                input.socd_modes[1].get_name().to_string(),
+            ],
+            _ => return vec![format!("{}", self.settings.network.port)],
+        }
+    }
+
+    fn refresh_elements(self: &mut Self) {
+        self.category_txt.text = CATEGORIES[self.category].to_string();
+        self.category_txt.centralize();
+
+        let names = SETTING_NAMES[self.category];
+        let values = self.get_values();
+        for i in 0..MAX_ROWS {
+            let is_used = i < names.len();
+            self.label_txts[i].text = if is_used { names[i].to_string() } else { String::new() };
+            self.value_txts[i].text = if is_used { values[i].clone() } else { String::new() };
+            self.value_txts[i].centralize();
+            for button in self.setting_btns[i].iter_mut() { button.enabled = is_used; }
+        }
+    }
+
+    fn get_rows_amount(self: &Self) -> usize {
+        return SETTING_NAMES[self.category].len();
+    }
+
+    pub fn new() -> OptionsScreen {
+        let row_pos = |i: usize| 0.35 + i as f32 * 0.1;
+
+        let mut screen = OptionsScreen {
+            title_txt: Text::new("Options", Vector2::new(0.5, 0.08), Color::WHITE, 20),
+            category_txt: Text::new("", Vector2::new(0.5, 0.2), Color::WHITE, 20),
+            category_btns: vec![
+                Button::new(true, "<", Vector2::new(0.3, 0.2)),
+                Button::new(true, ">", Vector2::new(0.7, 0.2))
+            ],
+            label_txts: (0..MAX_ROWS).map(|i| Text::new("", Vector2::new(0.2, row_pos(i)), Color::WHITE, 18)).collect(),
+            value_txts: (0..MAX_ROWS).map(|i| Text::new("", Vector2::new(0.6, row_pos(i)), Color::GOLD, 18)).collect(),
+            setting_btns: (0..MAX_ROWS).map(|i| vec![
+                Button::new(true, "<", Vector2::new(0.45, row_pos(i))),
+                Button::new(true, ">", Vector2::new(0.75, row_pos(i)))
+            ]).collect(),
+            back_btn: Button::new(true, "Back", Vector2::new(0.5, 0.88)),
+
+            settings: Settings::load(),
+            revision: Settings::get_revision(),
+            category: 0,
+            is_active: true,
+        };
+
+        screen.refresh_elements();
+        return screen;
+    }
+}
+
+impl UIScreen for OptionsScreen {
+    fn update(self: &mut Self, rl: &RaylibHandle) {
+        self.check_revision();
+        if self.back_btn.is_pressed(rl) { self.is_active = false; }
+
+             if self.category_btns[0].is_pressed(rl) { self.change_category(-1); }
+        else if self.category_btns[1].is_pressed(rl) { self.change_category( 1); }
+
+        for i in 0..self.get_rows_amount() {
+                 if self.setting_btns[i][0].is_pressed(rl) { self.change_setting(i, -1); }
+            else if self.setting_btns[i][1].is_pressed(rl) { self.change_setting(i,  1); }
+        }
+    }
+
+    fn get_elements(self: &mut Self, rl: &RaylibHandle) -> ScreenElements {
+        let rows = self.get_rows_amount();
+        let mut texts = vec![self.title_txt.clone(), self.category_txt.clone()];
+        texts.extend(self.label_txts[..rows].iter().cloned());
+        texts.extend(self.value_txts[..rows].iter().cloned());
+
+        let mut buttons = vec![self.back_btn.clone()];
+        buttons.append(&mut self.category_btns.clone());
+        for row in &self.setting_btns[..rows] { buttons.append(&mut row.clone()); }
+        return ScreenElements::new(rl, texts, buttons, vec![]);
+    }
+
+    fn get_focus_order(self: &mut Self) -> Vec<Vec<&mut dyn Focusable>> {
+        let rows = self.get_rows_amount();
+        let mut order = vec![FocusNavigator::row(&mut self.category_btns)];
+        order.extend(self.setting_btns[..rows].iter_mut().map(|r| FocusNavigator::row(r)));
+        order.push(vec![&mut self.back_btn]);
+        return order;
+    }
+
+    fn get_previous_screen(&self) -> Option<Box<dyn UIScreen>> {
+        return Some(Box::new(TitleScreen::new()));
+    }
+
+    fn goes_to_scene(&self) -> bool { false }
+    fn is_active(&self) -> bool { self.is_active }
+    fn get_next_screen(&self, rl: &RaylibHandle) -> Box<dyn UIScreen> {
+        return Box::new(TitleScreen::new());
+    }
+    fn get_next_scene(&self, rl: &RaylibHandle) -> Box<dyn GameScene> {
+        panic!("This screen doesn't lead to a scene, should've called 'get_next_screen' instead.");
+    }
+}
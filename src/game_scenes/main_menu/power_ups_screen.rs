@@ -0,0 +1,86 @@
+use super::*;
+
+impl PowerUpsScreen {
+    fn toggle(self: &mut Self, index: usize) {
+        let power_up = PowerUp::ALL[index];
+        if self.enabled.contains(&power_up) { self.enabled.retain(|p| *p != power_up); }
+        else { self.enabled.push(power_up); }
+
+        let mut data = PowerUpData::load();
+        data.set_enabled(self.selected_gamemode, self.enabled.clone());
+        data.save();
+        self.refresh_toggles();
+    }
+
+    fn refresh_toggles(self: &mut Self) {
+        for (i, power_up) in PowerUp::ALL.iter().enumerate() {
+            let text = if self.enabled.contains(power_up) { "On" } else { "Off" };
+            self.toggle_btns[i] = Button::new(true, text, Vector2::new(0.65, Self::get_row_pos(i)));
+        }
+    }
+
+    fn get_row_pos(index: usize) -> f32 {
+        return 0.2 + index as f32 * 0.1;
+    }
+
+    fn get_stage_screen(self: &Self) -> Box<dyn UIScreen> {
+        return Box::new(StageScreen::new(self.selected_gamemode, self.rules.clone(), self.devices.clone()));
+    }
+
+    pub fn new(mode: GameMode, rules: GameRules, devices: (DeviceId, DeviceId)) -> PowerUpsScreen {
+        let mut screen = PowerUpsScreen {
+            title_txt: Text::new(&format!("{} Power-ups", mode.get_name()), Vector2::new(0.5, 0.08), Color::WHITE, 20),
+            label_txts: PowerUp::ALL.iter().enumerate().map(|(i, power_up)| {
+                Text::new(power_up.get_name(), Vector2::new(0.35, Self::get_row_pos(i)), power_up.get_color(), 18)
+            }).collect(),
+            toggle_btns: PowerUp::ALL.iter().map(|_| Button::new(true, "", Vector2::new(0.5, 0.5))).collect(),
+            back_btn: Button::new(true, "Back", Vector2::new(0.5, 0.88)),
+
+            enabled: PowerUpData::load().get_enabled(mode),
+            rules, devices,
+            is_active: true,
+            selected_gamemode: mode,
+        };
+
+        screen.refresh_toggles();
+        return screen;
+    }
+}
+
+impl UIScreen for PowerUpsScreen {
+    fn update(self: &mut Self, rl: &RaylibHandle) {
+        if self.back_btn.is_pressed(rl) { self.is_active = false; }
+
+        for i in 0..self.toggle_btns.len() {
+            if self.toggle_btns[i].is_pressed(rl) { self.toggle(i); }
+        }
+    }
+
+    fn get_elements(self: &mut Self, rl: &RaylibHandle) -> ScreenElements {
+        let mut texts = vec![self.title_txt.clone()];
+        texts.append(&mut self.label_txts.clone());
+
+        let mut buttons = vec![self.back_btn.clone()];
+        buttons.append(&mut self.toggle_btns.clone());
+        return ScreenElements::new(rl, texts, buttons, vec![]);
+    }
+
+    fn get_focus_order(self: &mut Self) -> Vec<Vec<&mut dyn Focusable>> {
+        let mut rows: Vec<Vec<&mut dyn Focusable>> = self.toggle_btns.iter_mut().map(|b| vec![b as &mut dyn Focusable]).collect();
+        rows.push(vec![&mut self.back_btn]);
+        return rows;
+    }
+
+    fn get_previous_screen(&self) -> Option<Box<dyn UIScreen>> {
+        return Some(self.get_stage_screen());
+    }
+
+    fn goes_to_scene(&self) -> bool { false }
+    fn is_active(&self) -> bool { self.is_active }
+    fn get_next_screen(&self, rl: &RaylibHandle) -> Box<dyn UIScreen> {
+        return self.get_stage_screen();
+    }
+    fn get_next_scene(&self, rl: &RaylibHandle) -> Box<dyn GameScene> {
+        panic!("This screen doesn't lead to a scene, should've called 'get_next_screen' instead.");
+    }
+}
@@ -0,0 +1,120 @@
+use super::*;
+
+const PREVIEW_SCALE: f32 = 0.6;
+
+impl StageScreen {
+    fn change_stage(self: &mut Self, step: i32) {
+        let amount = self.stages.len() as i32;
+        self.selected_stage = (self.selected_stage as i32 + step).rem_euclid(amount) as usize;
+        self.preview_time = 0.0;
+
+        self.stage_txt.text = self.stages[self.selected_stage].name.clone();
+        self.stage_txt.centralize();
+    }
+
+    // The arena shrunk down, with its paddles, so stages can be told apart before playing
+    fn get_preview(self: &Self) -> Vec<Shape> {
+        let stage = &self.stages[self.selected_stage];
+        let size = SCREEN_SIZE * PREVIEW_SCALE;
+        let area = Rectangle::new((SCREEN_SIZE.x - size.x) / 2.0, SCREEN_SIZE.y * 0.13, size.x, size.y);
+
+        let mut shapes = stage.get_shapes(self.preview_time, area);
+        shapes.push(Shape::RectangleLines(area, Color::GRAY));
+
+        let paddle_size = PADDLE_SIZE * PREVIEW_SCALE;
+        let paddle_y = area.y + (size.y - paddle_size.y) / 2.0;
+        for x in [stage.paddle_padding, SCREEN_SIZE.x - PADDLE_SIZE.x - stage.paddle_padding] {
+            let paddle = Rectangle::new(area.x + x * PREVIEW_SCALE, paddle_y, paddle_size.x, paddle_size.y);
+            shapes.push(Shape::Rectangle(paddle, Color::new(255, 105, 97, 130)));
+        }
+        return shapes;
+    }
+
+    fn get_rules(self: &Self) -> GameRules {
+        let mut rules = self.rules.clone();
+        rules.stage = self.stages[self.selected_stage].clone();
+        return rules;
+    }
+
+    pub fn new(mode: GameMode, rules: GameRules, devices: (DeviceId, DeviceId)) -> StageScreen {
+        let stages = Stage::load_all();
+        let selected_stage = stages.iter().position(|s| s.name == rules.stage.name).unwrap_or(0);
+
+        let mut screen = StageScreen {
+            title_txt: Text::new("Select Stage:", Vector2::new(0.5, 0.06), Color::WHITE, 20),
+            stage_txt: Text::new("", Vector2::new(0.5, 0.78), Color::WHITE, 20),
+            stage_btns: vec![
+                Button::new(true, "<", Vector2::new(0.3, 0.78)),
+                Button::new(true, ">", Vector2::new(0.7, 0.78))
+            ],
+            power_ups_btn: Button::new(true, "Power-ups", Vector2::new(0.35, 0.88)),
+            start_btn: Button::new(true, "Start", Vector2::new(0.65, 0.88)),
+
+            stages, selected_stage,
+            preview_time: 0.0,
+
+            rules, devices,
+            is_active: true,
+            next_screen: None,
+            selected_gamemode: mode,
+        };
+
+        screen.change_stage(0);
+        return screen;
+    }
+}
+
+impl UIScreen for StageScreen {
+    fn get_next_scene(&self, rl: &RaylibHandle) -> Box<dyn GameScene> {
+        let mut rules = self.get_rules();
+        rules.power_ups = PowerUpData::load().get_enabled(self.selected_gamemode);
+
+        let hiscore = SaveData::load().get_highscore(self.selected_gamemode, Difficulty::Normal);
+        let devices = (DeviceRegistry::create_device(&self.devices.0), DeviceRegistry::create_device(&self.devices.1));
+        return Box::new(GameLoop::new(self.selected_gamemode, rules, devices, hiscore, Settings::load().input));
+    }
+
+    fn update(self: &mut Self, rl: &RaylibHandle) {
+        self.preview_time += rl.get_frame_time();
+        if self.start_btn.is_pressed(rl) { self.is_active = false; }
+        if self.power_ups_btn.is_pressed(rl) {
+            self.next_screen = Some(MenuScreen::PowerUpsScreen);
+            self.is_active = false;
+        }
+
+             if self.stage_btns[0].is_pressed(rl) { self.change_stage(-1); }
+        else if self.stage_btns[1].is_pressed(rl) { self.change_stage( 1); }
+    }
+
+    fn get_elements(self: &mut Self, rl: &RaylibHandle) -> ScreenElements {
+        let mut buttons: Vec<Button> = vec![self.power_ups_btn.clone(), self.start_btn.clone()];
+        buttons.append(&mut self.stage_btns.clone());
+
+        let mut elements = ScreenElements::new(rl, vec![self.title_txt.clone(), self.stage_txt.clone()], buttons, vec![]);
+        elements.shapes = self.get_preview();
+        return elements;
+    }
+
+    fn get_focus_order(self: &mut Self) -> Vec<Vec<&mut dyn Focusable>> {
+        return vec![
+            FocusNavigator::row(&mut self.stage_btns),
+            vec![&mut self.power_ups_btn as &mut dyn Focusable, &mut self.start_btn]
+        ];
+    }
+
+    fn get_previous_screen(&self) -> Option<Box<dyn UIScreen>> {
+        if self.selected_gamemode == GameMode::Multiplayer {
+            return Some(Box::new(VersusScreen::new(self.get_rules(), self.devices.clone())));
+        }
+        return Some(Box::new(DeviceScreen::new(self.selected_gamemode)));
+    }
+
+    fn goes_to_scene(&self) -> bool { self.next_screen.is_none() }
+    fn is_active(&self) -> bool { self.is_active }
+    fn get_next_screen(&self, rl: &RaylibHandle) -> Box<dyn UIScreen> {
+        match self.next_screen {
+            Some(MenuScreen::PowerUpsScreen) => return Box::new(PowerUpsScreen::new(self.selected_gamemode, self.get_rules(), self.devices.clone())),
+            _ => panic!("Invalid next screen, how did you manage to do this?")
+        }
+    }
+}
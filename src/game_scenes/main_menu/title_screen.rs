@@ -1,26 +1,48 @@
 use super::*;
 
 impl TitleScreen {
+    // Each solo mode has its own hiscore, shown under the menu
+    fn change_solo_mode(self: &mut Self, step: i32) {
+        let modes = GameMode::SOLO;
+        let index = modes.iter().position(|m| *m == self.solo_mode).unwrap_or(0) as i32;
+        self.solo_mode = modes[(index + step).rem_euclid(modes.len() as i32) as usize];
+
+        self.singleplayer_btn = Button::new(true, self.solo_mode.get_name(), Vector2::new(0.5, 0.4));
+        self.hiscore_txt.text = format!("{} HiScore: {}", self.solo_mode.get_name(), 
+                                        SaveData::load().get_highscore(self.solo_mode, Difficulty::Normal));
+        self.hiscore_txt.centralize();
+    }
+
     pub fn new() -> TitleScreen {
-        TitleScreen {
+        let mut screen = TitleScreen {
             title_txt: Text::new(
                 "Pong 2: The Enemy is Now Another", Vector2::new(0.5, 0.1), 
                 Color::GOLD, 26
             ),
             
             hiscore_txt: Text::new(
-                &format!("HiScore: {}", MiscUtils::get_highscore()), Vector2::new(0.5, 0.95),
+                "", Vector2::new(0.5, 0.95),
                 Color::WHITE, 16
             ),
 
             singleplayer_btn: Button::new(true, "Singleplayer", Vector2::new(0.5, 0.4)),
+            solo_mode_btns: vec![
+                Button::new(true, "<", Vector2::new(0.25, 0.4)),
+                Button::new(true, ">", Vector2::new(0.75, 0.4))
+            ],
             multiplayer_btn: Button::new(true, "Multiplayer", Vector2::new(0.5, 0.5)),
-            quit_btn: Button::new(true, "Quit", Vector2::new(0.5, 0.6)),
+            controls_btn: Button::new(true, "Controls", Vector2::new(0.5, 0.6)),
+            options_btn: Button::new(true, "Options", Vector2::new(0.5, 0.7)),
+            quit_btn: Button::new(true, "Quit", Vector2::new(0.5, 0.8)),
             
             is_active: true,
             selected_mode: GameMode::None,
+            solo_mode: GameMode::Singleplayer,
             next_screen: MenuScreen::TitleScreen,
-        }
+        };
+
+        screen.change_solo_mode(0);
+        return screen;
     }
 }
 
@@ -29,13 +51,18 @@ impl UIScreen for TitleScreen {
         match self.next_screen {
             MenuScreen::DeviceScreen => return Box::new(DeviceScreen::new(self.selected_mode)),
             MenuScreen::MultiplayerScreen => return Box::new(MultiplayerScreen::new()),
+            MenuScreen::ControlsScreen => return Box::new(ControlsScreen::new(DeviceId::Keyboard(true))),
+            MenuScreen::OptionsScreen => return Box::new(OptionsScreen::new()),
             _ => panic!("Invalid next screen, how did you manage to do this?")
         }
     }
 
     fn update(self: &mut Self, rl: &RaylibHandle) {
+             if self.solo_mode_btns[0].is_pressed(rl) { self.change_solo_mode(-1); }
+        else if self.solo_mode_btns[1].is_pressed(rl) { self.change_solo_mode( 1); }
+
         if self.singleplayer_btn.is_pressed(&rl) { 
-            self.selected_mode = GameMode::Singleplayer;
+            self.selected_mode = self.solo_mode;
             self.next_screen = MenuScreen::DeviceScreen;
             self.is_active = false;
             return; 
@@ -48,6 +75,18 @@ impl UIScreen for TitleScreen {
             return;
         }
 
+        if self.controls_btn.is_pressed(&rl) { 
+            self.next_screen = MenuScreen::ControlsScreen;
+            self.is_active = false;
+            return;
+        }
+
+        if self.options_btn.is_pressed(rl) {
+            self.next_screen = MenuScreen::OptionsScreen;
+            self.is_active = false;
+            return;
+        }
+
         if self.quit_btn.is_pressed(&rl) { 
             todo!("Implement this");
         }
@@ -57,11 +96,24 @@ impl UIScreen for TitleScreen {
     fn get_elements(self: &mut Self, rl: &RaylibHandle) -> ScreenElements {
         ScreenElements::new(rl,
             vec![self.title_txt.clone(), self.hiscore_txt.clone()],
-            vec![self.singleplayer_btn.clone(), self.multiplayer_btn.clone(), self.quit_btn.clone()], 
+            vec![self.singleplayer_btn.clone(), self.solo_mode_btns[0].clone(), self.solo_mode_btns[1].clone(),
+                 self.multiplayer_btn.clone(), self.controls_btn.clone(), self.options_btn.clone(), self.quit_btn.clone()], 
             vec![]
         )
     }
 
+    fn get_focus_order(self: &mut Self) -> Vec<Vec<&mut dyn Focusable>> {
+        let (left, right) = self.solo_mode_btns.split_at_mut(1);
+        return vec![
+            vec![&mut left[0] as &mut dyn Focusable, &mut self.singleplayer_btn, &mut right[0]],
+            vec![&mut self.multiplayer_btn],
+            vec![&mut self.controls_btn],
+            vec![&mut self.options_btn],
+            vec![&mut self.quit_btn]
+        ];
+    }
+
+    fn get_previous_screen(&self) -> Option<Box<dyn UIScreen>> { None }
     fn goes_to_scene(&self) -> bool { false }
     fn is_active(&self) -> bool { self.is_active }
     fn get_next_scene(&self, rl: &RaylibHandle) -> Box<dyn GameScene> {
@@ -0,0 +1,118 @@
+use super::*;
+
+const SETTING_NAMES: [&str; 5] = ["Scoring", "Format", "Rounds", "Rally target", "Swap sides"];
+const MAX_ROUNDS: i32 = 9;
+const RALLY_STEP: i32 = 5;
+const MAX_RALLY_TARGET: i32 = 50;
+
+impl VersusScreen {
+    fn change_setting(self: &mut Self, setting: usize, step: i32) {
+        let versus = &mut self.match_rules;
+
+        match setting {
+            0 => self.is_versus = !self.is_versus,
+            1 => {
+                let formats = MatchFormat::ALL;
+                let index = formats.iter().position(|f| *f == versus.format).unwrap_or(0) as i32;
+                versus.format = formats[(index + step).rem_euclid(formats.len() as i32) as usize];
+            },
+            2 => versus.rounds = (versus.rounds - 1 + step).rem_euclid(MAX_ROUNDS) + 1,
+            3 => versus.rally_target = (versus.rally_target + step * RALLY_STEP).clamp(RALLY_STEP, MAX_RALLY_TARGET),
+            _ => versus.swap_sides = !versus.swap_sides,
+        }
+
+        self.refresh_elements();
+    }
+
+    fn refresh_elements(self: &mut Self) {
+        let versus = self.match_rules;
+        let values = [
+            if self.is_versus { "Versus".to_string() } else { "Classic".to_string() },
+            versus.format.get_name().to_string(),
+            format!("{}", versus.rounds),
+            format!("{}", versus.rally_target),
+            if versus.swap_sides { "On".to_string() } else { "Off".to_string() },
+        ];
+
+        // Classic only counts the ball player's bounces, so the match rules don't matter
+        for (i, value) in values.iter().enumerate() {
+            let enabled = i == 0 || self.is_versus;
+
+            self.value_txts[i].text = value.clone();
+            self.value_txts[i].color = if enabled { Color::GOLD } else { ScreenElements::DISABLED_COLOR };
+            self.value_txts[i].centralize();
+            for button in self.setting_btns[i].iter_mut() { button.enabled = enabled; }
+        }
+    }
+
+    fn get_rules(self: &Self) -> GameRules {
+        let mut rules = self.rules.clone();
+        rules.versus = if self.is_versus { Some(self.match_rules) } else { None };
+        return rules;
+    }
+
+    pub fn new(rules: GameRules, devices: (DeviceId, DeviceId)) -> VersusScreen {
+        let row_pos = |i: usize| 0.22 + i as f32 * 0.1;
+
+        let mut screen = VersusScreen {
+            title_txt: Text::new("Match Rules", Vector2::new(0.5, 0.08), Color::WHITE, 20),
+            label_txts: SETTING_NAMES.iter().enumerate().map(|(i, name)| {
+                Text::new(name, Vector2::new(0.2, row_pos(i)), Color::WHITE, 18)
+            }).collect(),
+            value_txts: (0..SETTING_NAMES.len()).map(|i| Text::new("", Vector2::new(0.6, row_pos(i)), Color::GOLD, 18)).collect(),
+            setting_btns: (0..SETTING_NAMES.len()).map(|i| vec![
+                Button::new(true, "<", Vector2::new(0.45, row_pos(i))),
+                Button::new(true, ">", Vector2::new(0.75, row_pos(i)))
+            ]).collect(),
+            next_btn: Button::new(true, "Next", Vector2::new(0.5, 0.86)),
+
+            is_versus: rules.versus.is_some(),
+            match_rules: rules.versus.unwrap_or(MatchRules::new()),
+            rules, devices,
+            is_active: true,
+        };
+
+        screen.refresh_elements();
+        return screen;
+    }
+}
+
+impl UIScreen for VersusScreen {
+    fn update(self: &mut Self, rl: &RaylibHandle) {
+        if self.next_btn.is_pressed(rl) { self.is_active = false; }
+
+        for i in 0..SETTING_NAMES.len() {
+                 if self.setting_btns[i][0].is_pressed(rl) { self.change_setting(i, -1); }
+            else if self.setting_btns[i][1].is_pressed(rl) { self.change_setting(i,  1); }
+        }
+    }
+
+    fn get_elements(self: &mut Self, rl: &RaylibHandle) -> ScreenElements {
+        let mut texts = vec![self.title_txt.clone()];
+        texts.append(&mut self.label_txts.clone());
+        texts.append(&mut self.value_txts.clone());
+
+        let mut buttons = vec![self.next_btn.clone()];
+        for row in &self.setting_btns { buttons.append(&mut row.clone()); }
+        return ScreenElements::new(rl, texts, buttons, vec![]);
+    }
+
+    fn get_focus_order(self: &mut Self) -> Vec<Vec<&mut dyn Focusable>> {
+        let mut rows: Vec<Vec<&mut dyn Focusable>> = self.setting_btns.iter_mut().map(|r| FocusNavigator::row(r)).collect();
+        rows.push(vec![&mut self.next_btn]);
+        return rows;
+    }
+
+    fn get_previous_screen(&self) -> Option<Box<dyn UIScreen>> {
+        return Some(Box::new(DeviceScreen::new(GameMode::Multiplayer)));
+    }
+
+    fn goes_to_scene(&self) -> bool { false }
+    fn is_active(&self) -> bool { self.is_active }
+    fn get_next_screen(&self, rl: &RaylibHandle) -> Box<dyn UIScreen> {
+        return Box::new(StageScreen::new(GameMode::Multiplayer, self.get_rules(), self.devices.clone()));
+    }
+    fn get_next_scene(&self, rl: &RaylibHandle) -> Box<dyn GameScene> {
+        panic!("This screen doesn't lead to a scene, should've called 'get_next_screen' instead.");
+    }
+}
@@ -1,46 +1,238 @@
 mod main_menu;
 mod game_loop;
+mod results_scene;
+mod winner_scene;
+mod pause_menu;
+mod dev_console;
+mod rules;
+mod power_ups;
+mod versus;
 use std::panic;
 
+use rand::rngs::StdRng;
 use raylib::prelude::*;
+use serde::{Serialize, Deserialize};
 use crate::game_objects::*;
+use crate::stage_system::Stage;
+use crate::render_system::Canvas;
 use crate::ui_system::*;
-use crate::input_system::PlayerInput;
+use crate::input_system::{PlayerInput, InputDevice, DeviceRegistry};
+use crate::networking::NetworkManager;
 
 use self::main_menu::*;
 
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum GameMode { 
+    None, Singleplayer, Multiplayer, 
+    TimeAttack,     // As many bounces as possible before the time runs out
+    Survival,       // One life and no checkpoints
+    Zen             // No lives, just practice
+}
+
+// Only one for now, but each one gets its own highscore table
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Difficulty { Normal }
+
+#[derive(Clone, Copy, Default)]
+pub struct GameStats {
+    pub best_score: i32,
+    pub best_checkpoint: i32,
+    pub max_tier: i32,
+
+    pub rally: i32,
+    pub longest_rally: i32,
+    pub total_bounces: i32,
+    pub time_played: f32,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum SceneType { MainMenu, GameLoop, ResultsScene, WinnerScene }
+
+// How the ball leaves a paddle
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BounceModel {
+    Random,     // Random slope, steeper near the top and bottom
+    English,    // Follows where the ball hit the paddle and how fast the paddle was moving
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum MatchFormat { FirstTo, BestOf }
+
+// Multiplayer played as rounds. The paddle player takes the round if the ball leaves the screen,
+// the ball player if the rally reaches the target
 #[derive(Clone, Copy, PartialEq)]
-pub enum GameMode { None, Singleplayer, Multiplayer }
+pub struct MatchRules {
+    pub format: MatchFormat,
+    pub rounds: i32,        // Rounds to win, or to play for best of
+    pub rally_target: i32,
+    pub swap_sides: bool,   // Players swap the ball and paddles after each round
+}
+
+// How a versus match is going
+#[derive(Clone, Copy, Default)]
+pub struct MatchScore {
+    pub points: [i32; 2],   // Rounds won by each player
+    pub rounds_played: i32,
+    pub ball_player: usize, // Who has the ball this round
+}
+
+// Options picked before a match, kept when it's restarted
+#[derive(Clone)]
+pub struct GameRules {
+    pub bounce_model: BounceModel,
+    pub stage: Stage,
+    pub power_ups: Vec<PowerUp>,   // The ones that can spawn
+    pub versus: Option<MatchRules>, // Multiplayer just counts bounces without it
+}
+
+// What the ball bounced on
+#[derive(Clone, Copy, PartialEq)]
+pub enum Contact { Edge, Paddle, Obstacle, Bumper(f32) }
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum PauseAction { None, Resume, Restart, Quit }
 
 pub trait GameScene {
     fn is_active(&self) -> bool;
     fn get_next_scene(&self, rl: &RaylibHandle) -> Box<dyn GameScene>;
     
     fn update(&mut self, rl: &RaylibHandle);
-    fn draw(&mut self, rl: &mut RaylibHandle, thread: &RaylibThread); // Needs to be the last called method, since it drops the RaylibHandle
+    fn draw(&mut self, canvas: &mut Canvas); // Scaled to the window once the scene is drawn
+
+    // Tier the music plays at, no music if None
+    fn get_music_level(&self) -> Option<i32> { return None; }
 }
 
 pub struct GameLoop {    
     score: i32,
     hiscore: i32,
+    stats: GameStats,
     score_color: Color,
     
     ball: Ball,
+    tier: i32,
     checkpoint: i32,
     respawn_timer: f32,
     bounced_vertically: bool,
     
     left_paddle: Paddle,
     right_paddle: Paddle,
+    paddle_speed: f32,  // Of both paddles, before any power-up slows them down
+    ball_kick: f32,     // Extra speed from bumpers, wearing off
+    in_portal: bool,    // Portals only work again once the ball leaves them
+
+    pickups: Vec<Pickup>,
+    pickup_timer: f32,                  // Seconds until the next pickup spawns
+    active_power_ups: Vec<(PowerUp, f32)>, // With the seconds they have left
+    match_score: MatchScore,
     
     players_input: Vec<PlayerInput>,
+    devices: DeviceRegistry,
+    settings_revision: u32, // Of the input settings the players are using
 
     is_active: bool,
+    is_paused: bool,
+    network: Option<NetworkManager>, // Online peer, always None until online play can start a session
+    pause_menu: PauseMenu,
+    next_scene: SceneType,
+
     debug_mode: bool,
+    input_lag: [f32; 2], // Milliseconds from sampling each player's input to showing its frame
+    console: DevConsole,
+    tunables: Tunables,
+    rng: StdRng,
     game_mode: GameMode,
+    difficulty: Difficulty,
+    rules: GameRules,
+}
+
+// Values the dev console can change while playing, starting at the game's constants
+#[derive(Clone, Copy)]
+pub struct Tunables {
+    pub max_player_speed: f32,
+    pub paddle_range: f32,
+
+    pub edge_exponent: f32,     // Curve from the entry angle to the bounce angle
+    pub edge_min_angle: f32,
+    pub edge_max_angle: f32,
+
+    pub paddle_edge_zone: f32,  // Distance from the top or bottom where paddle bounces get steeper
+    pub paddle_min_angle: f32,
+    pub paddle_max_angle: f32,
+    pub english_spin: f32,      // How much of the paddle's speed carries into english bounces
+}
+
+// A value the console can read and change by name
+pub struct Tunable {
+    pub name: &'static str,
+    pub get: fn(&GameLoop) -> f32,
+    pub set: fn(&mut GameLoop, f32),
+    pub is_positive: bool, // Values at or below 0 are rejected
+}
+
+pub struct DevConsole {
+    pub is_open: bool,
+    field: TextField,
+    log: Vec<String>,
+    history: Vec<String>,
+    history_index: Option<usize>, // Command being browsed with up and down
+}
+
+pub struct PauseMenu {
+    title_txt: Text,
+    resume_btn: Button,
+    restart_btn: Button,
+    options_btn: Button,
+    quit_btn: Button,
+    missing_device: Option<String>, // Can't resume until it's plugged back
+    options: Option<OptionsScreen>, // Shown instead of the menu while it's open
+    focus: FocusNavigator,
+}
+
+pub struct ResultsScene {
+    game_mode: GameMode,
+    difficulty: Difficulty,
+    rules: GameRules,
+    stats: GameStats,
+    rank: Option<usize>,
+    devices: (Box<dyn InputDevice>, Box<dyn InputDevice>),
+
+    title_txt: Text,
+    score_txt: Text,
+    stats_txts: Vec<Text>,
+    prompt_txt: Text,
+    table_txts: Vec<Text>,
+
+    initials_field: InitialsField,
+    retry_btn: Button,
+    menu_btn: Button,
+    focus: FocusNavigator,
+    
+    is_active: bool,
+    retry: bool,
+}
+
+pub struct WinnerScene {
+    rules: GameRules,
+    devices: (Box<dyn InputDevice>, Box<dyn InputDevice>),
+
+    title_txt: Text,
+    score_txt: Text,
+    rematch_btn: Button,
+    menu_btn: Button,
+    focus: FocusNavigator,
+
+    is_active: bool,
+    rematch: bool,
 }
 
 pub struct MainMenu {
     current_screen: Box<dyn UIScreen>,
-    is_active: bool
+    focus: FocusNavigator,
+    is_active: bool,
+
+    // Every keyboard and gamepad can navigate the menus, with the bindings saved for them
+    devices: DeviceRegistry,
+    menu_devices: Vec<Box<dyn InputDevice>>,
+    profiles_revision: u32,
 }
\ No newline at end of file
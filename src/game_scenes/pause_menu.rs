@@ -0,0 +1,71 @@
+use raylib::prelude::*;
+
+use crate::game_scenes::*;
+
+impl PauseMenu {
+    // Navigated with the players' devices
+    pub fn update(self: &mut Self, rl: &RaylibHandle, devices: &mut [&mut dyn InputDevice]) -> PauseAction {
+        if let Some(options) = &mut self.options {
+            let command = self.focus.update(rl, options.get_focus_order(), devices);
+            options.update(rl);
+
+            if command == NavCommand::Back || !options.is_active() {
+                self.options = None;
+                self.focus.reset();
+            }
+            return PauseAction::None;
+        }
+
+        let rows: Vec<Vec<&mut dyn Focusable>> = vec![
+            vec![&mut self.resume_btn], vec![&mut self.restart_btn], 
+            vec![&mut self.options_btn], vec![&mut self.quit_btn]
+        ];
+        
+        let command = self.focus.update(rl, rows, devices);
+        if command == NavCommand::Back && self.can_resume() { return PauseAction::Resume; }
+        if self.resume_btn.is_pressed(rl) { return PauseAction::Resume; }
+        if self.restart_btn.is_pressed(rl) { return PauseAction::Restart; }
+        if self.options_btn.is_pressed(rl) {
+            self.options = Some(OptionsScreen::new());
+            self.focus.reset();
+        }
+        if self.quit_btn.is_pressed(rl) { return PauseAction::Quit; }
+        return PauseAction::None;
+    }
+
+    // Leaving the options goes back to the menu instead
+    pub fn can_resume(self: &Self) -> bool {
+        return self.missing_device.is_none() && self.options.is_none();
+    }
+
+    pub fn set_missing_device(self: &mut Self, device_name: Option<String>) {
+        self.title_txt.text = match &device_name {
+            Some(name) => format!("Reconnect {}", name),
+            None => "Paused".to_string()
+        };
+        self.title_txt.centralize();
+        self.resume_btn.enabled = device_name.is_none();
+        self.missing_device = device_name;
+    }
+
+    pub fn get_elements(self: &mut Self, rl: &RaylibHandle) -> ScreenElements {
+        if let Some(options) = &mut self.options { return options.get_elements(rl); }
+        return ScreenElements::new(rl, vec![self.title_txt.clone()], 
+            vec![self.resume_btn.clone(), self.restart_btn.clone(), self.options_btn.clone(), self.quit_btn.clone()], 
+            vec![]
+        );
+    }
+
+    pub fn new() -> PauseMenu {
+        return PauseMenu {
+            title_txt: Text::new("Paused", Vector2::new(0.5, 0.25), Color::WHITE, 26),
+            resume_btn: Button::new(true, "Resume", Vector2::new(0.5, 0.4)),
+            restart_btn: Button::new(true, "Restart", Vector2::new(0.5, 0.5)),
+            options_btn: Button::new(true, "Options", Vector2::new(0.5, 0.6)),
+            quit_btn: Button::new(true, "Quit to Menu", Vector2::new(0.5, 0.7)),
+            missing_device: None,
+            options: None,
+            focus: FocusNavigator::new(),
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use rand::Rng;
+use raylib::prelude::*;
+
+use crate::utils::*;
+use crate::game_objects::*;
+use crate::game_scenes::*;
+
+const PICKUP_RADIUS: f32 = 12.0;
+const PICKUP_LIFETIME: f32 = 8.0;
+pub const PICKUP_INTERVAL: (f32, f32) = (5.0, 9.0); // Random seconds between pickups
+const MAX_PICKUPS: usize = 2;
+
+const SHRINK_SCALE: f32 = 0.6;
+const PADDLE_SLOW_SCALE: f32 = 0.5;
+const SCORE_MULTIPLIER: i32 = 2;
+
+impl GameLoop {
+    // Counts down pickups and active power-ups, collects what the ball touches and spawns new pickups
+    pub fn update_power_ups(self: &mut Self, dt: f32) {
+        for (_, time_left) in &mut self.active_power_ups { *time_left -= dt; }
+        let expired: Vec<PowerUp> = self.active_power_ups.iter().filter(|(_, t)| *t <= 0.0).map(|(p, _)| *p).collect();
+        for power_up in expired { self.end_power_up(power_up); }
+
+        for pickup in &mut self.pickups { pickup.lifetime -= dt; }
+        self.pickups.retain(|p| p.lifetime > 0.0);
+
+        let collected: Vec<PowerUp> = self.pickups.iter().filter(|p| p.is_touching(&self.ball)).map(|p| p.power_up).collect();
+        self.pickups.retain(|p| !p.is_touching(&self.ball));
+        for power_up in collected { self.start_power_up(power_up); }
+
+        if self.rules.power_ups.is_empty() || self.pickups.len() >= MAX_PICKUPS { return; }
+        self.pickup_timer -= dt;
+        if self.pickup_timer <= 0.0 { self.spawn_pickup(); }
+    }
+
+    // Somewhere in the middle that isn't inside the stage, giving up after a few tries
+    fn spawn_pickup(self: &mut Self) {
+        self.pickup_timer = self.rng.gen_range(PICKUP_INTERVAL.0..PICKUP_INTERVAL.1);
+        let power_up = self.rules.power_ups[self.rng.gen_range(0..self.rules.power_ups.len())];
+        let colliders = self.rules.stage.get_colliders(self.stats.time_played);
+
+        for _ in 0..5 {
+            let position = Vector2::new(self.rng.gen_range(0.25..0.75) * SCREEN_SIZE.x, self.rng.gen_range(0.15..0.85) * SCREEN_SIZE.y);
+            if colliders.iter().any(|(c, _)| c.get_overlap(position, PICKUP_RADIUS).is_some()) { continue; }
+
+            self.pickups.push(Pickup::new(power_up, position, PICKUP_RADIUS, PICKUP_LIFETIME));
+            return;
+        }
+    }
+
+    pub fn has_power_up(self: &Self, power_up: PowerUp) -> bool {
+        return self.active_power_ups.iter().any(|(p, _)| *p == power_up);
+    }
+
+    // How much the ball grows for each life, less while it's shrunk
+    pub fn get_life_radius(self: &Self) -> f32 {
+        return LIFE_RADIUS * if self.has_power_up(PowerUp::Shrink) { SHRINK_SCALE } else { 1.0 };
+    }
+
+    // Collecting one that's already active just restarts its timer
+    pub fn start_power_up(self: &mut Self, power_up: PowerUp) {
+        if let Some(active) = self.active_power_ups.iter_mut().find(|(p, _)| *p == power_up) {
+            active.1 = power_up.get_duration();
+            return;
+        }
+
+        match power_up {
+            PowerUp::ExtraLife => {
+                if self.ball.lives < self.game_mode.get_lives().min(self.ball.colors.len() as i32) {
+                    self.ball.lives += 1;
+                    self.ball.radius += self.get_life_radius();
+                }
+                return;
+            },
+            PowerUp::Shrink => self.ball.radius *= SHRINK_SCALE,
+            PowerUp::PaddleSlow | PowerUp::Shield | PowerUp::ScoreMultiplier | PowerUp::Ghost => {},
+        }
+        self.active_power_ups.push((power_up, power_up.get_duration()));
+        if power_up == PowerUp::PaddleSlow { self.update_paddle_speed(); }
+    }
+
+    // Undoes whatever the power-up changed
+    fn end_power_up(self: &mut Self, power_up: PowerUp) {
+        if !self.has_power_up(power_up) { return; }
+        self.active_power_ups.retain(|(p, _)| *p != power_up);
+
+        match power_up {
+            PowerUp::Shrink => self.ball.radius /= SHRINK_SCALE,
+            PowerUp::PaddleSlow => self.update_paddle_speed(),
+            _ => {},
+        }
+    }
+
+    // Losing the ball ends every power-up and takes the pickups away
+    pub fn clear_power_ups(self: &mut Self) {
+        let active: Vec<PowerUp> = self.active_power_ups.iter().map(|(p, _)| *p).collect();
+        for power_up in active { self.end_power_up(power_up); }
+        self.pickups.clear();
+    }
+
+    // Paddles move at the tier's speed, slowed down while the power-up lasts
+    pub fn update_paddle_speed(self: &mut Self) {
+        let scale = if self.has_power_up(PowerUp::PaddleSlow) { PADDLE_SLOW_SCALE } else { 1.0 };
+        for paddle in [&mut self.left_paddle, &mut self.right_paddle] { paddle.speed = self.paddle_speed * scale; }
+    }
+
+    pub fn get_score_multiplier(self: &Self) -> i32 {
+        return if self.has_power_up(PowerUp::ScoreMultiplier) { SCORE_MULTIPLIER } else { 1 };
+    }
+
+    // The paddle the ball is heading to, which a ghost ball goes through
+    pub fn is_ghosted(self: &Self, is_left_paddle: bool) -> bool {
+        return self.has_power_up(PowerUp::Ghost) && is_left_paddle == (self.ball.prone_dir.x < 0.0);
+    }
+
+    // Pickups blink before they're gone, and a shield guards both sides
+    pub fn get_power_up_shapes(self: &Self) -> Vec<Shape> {
+        let mut shapes = Vec::new();
+
+        for pickup in &self.pickups {
+            if pickup.lifetime < 2.0 && (pickup.lifetime * 5.0) as i32 % 2 == 0 { continue; }
+            shapes.push(Shape::Circle(pickup.position, pickup.radius, pickup.power_up.get_color()));
+            shapes.push(Shape::CircleLines(pickup.position, pickup.radius + 3.0, Color::WHITE));
+        }
+
+        if self.has_power_up(PowerUp::Shield) {
+            let color = PowerUp::Shield.get_color();
+            shapes.push(Shape::Rectangle(Rectangle::new(0.0, 0.0, 3.0, SCREEN_SIZE.y), color));
+            shapes.push(Shape::Rectangle(Rectangle::new(SCREEN_SIZE.x - 3.0, 0.0, 3.0, SCREEN_SIZE.y), color));
+        }
+        return shapes;
+    }
+
+    // Letters on the pickups, and the active power-ups with their time left at the bottom
+    pub fn get_power_up_texts(self: &Self) -> Vec<Text> {
+        let mut texts: Vec<Text> = self.pickups.iter().map(|pickup| {
+            let mut text = Text::new(pickup.power_up.get_letter(), pickup.position / SCREEN_SIZE, Color::BLACK, 16);
+            text.pos.y -= 8.0;
+            return text;
+        }).collect();
+
+        for (i, (power_up, time_left)) in self.active_power_ups.iter().enumerate() {
+            let line = format!("{} {:.0}s", power_up.get_name(), time_left.ceil());
+            let pos = Vector2::new((i as f32 + 1.0) / (self.active_power_ups.len() as f32 + 1.0), 0.95);
+            texts.push(Text::new(&line, pos, power_up.get_color(), 16));
+        }
+        return texts;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extra_lives_are_capped() {
+        let mut game = GameLoop::new_scripted(GameMode::Singleplayer, GameRules::new());
+        let radius = game.ball.radius;
+        game.start_power_up(PowerUp::ExtraLife);
+
+        assert_eq!(game.ball.lives, 3);
+        assert_eq!(game.ball.radius, radius);
+    }
+
+    #[test]
+    fn power_ups_are_undone_when_they_run_out() {
+        let mut game = GameLoop::new_scripted(GameMode::Singleplayer, GameRules::new());
+        let radius = game.ball.radius;
+        game.start_power_up(PowerUp::Shrink);
+        assert!(game.ball.radius < radius);
+
+        game.update_power_ups(PowerUp::Shrink.get_duration());
+        assert!(!game.has_power_up(PowerUp::Shrink));
+        assert!((game.ball.radius - radius).abs() < 1e-4);
+    }
+
+    #[test]
+    fn paddles_stay_slow_through_new_tiers() {
+        let mut game = GameLoop::new_scripted(GameMode::Singleplayer, GameRules::new());
+        game.start_power_up(PowerUp::PaddleSlow);
+        game.run_command("tier 10");
+        assert_eq!(game.left_paddle.speed, INITIAL_PADDLE_SPEED * 0.9 * PADDLE_SLOW_SCALE);
+
+        game.clear_power_ups();
+        assert_eq!(game.left_paddle.speed, INITIAL_PADDLE_SPEED * 0.9);
+    }
+
+    #[test]
+    fn slow_paddles_follow_their_base_speed() {
+        let mut game = GameLoop::new_scripted(GameMode::Singleplayer, GameRules::new());
+        game.start_power_up(PowerUp::PaddleSlow);
+        game.run_command("set paddle.speed 300");
+        assert_eq!(game.right_paddle.speed, 300.0 * PADDLE_SLOW_SCALE);
+
+        game.update_power_ups(PowerUp::PaddleSlow.get_duration());
+        assert_eq!(game.right_paddle.speed, 300.0);
+    }
+
+    #[test]
+    fn multiplied_scores_dont_skip_checkpoints() {
+        let mut game = GameLoop::new_scripted(GameMode::Singleplayer, GameRules::new());
+        game.run_command("tier 9");
+        game.start_power_up(PowerUp::ScoreMultiplier);
+        game.add_score();
+
+        assert_eq!(game.score, 11);
+        assert_eq!(game.checkpoint, 10);
+    }
+}
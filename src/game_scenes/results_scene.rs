@@ -0,0 +1,132 @@
+use raylib::prelude::*;
+
+use crate::utils::*;
+use crate::save_system::*;
+use crate::settings_system::Settings;
+use crate::game_scenes::*;
+
+impl GameScene for ResultsScene {
+    fn update(self: &mut Self, rl: &RaylibHandle) {
+        if !self.initials_field.is_confirmed {
+            self.initials_field.update(rl);
+            if self.initials_field.is_confirmed { self.save_entry(); }
+            return;
+        }
+
+        let rows: Vec<Vec<&mut dyn Focusable>> = vec![vec![&mut self.retry_btn], vec![&mut self.menu_btn]];
+        let command = self.focus.update(rl, rows, &mut [self.devices.0.as_mut(), self.devices.1.as_mut()]);
+
+        if self.retry_btn.is_pressed(rl) {
+            self.retry = true;
+            self.is_active = false;
+        }
+        else if self.menu_btn.is_pressed(rl) || command == NavCommand::Back {
+            self.is_active = false;
+        }
+    }
+
+    fn draw(&mut self, canvas: &mut Canvas) {
+        let elements = self.get_elements(canvas);
+        canvas.clear_background(Color::BLACK);
+        elements.draw(canvas);
+    }
+
+    fn is_active(&self) -> bool { return self.is_active; }
+    fn get_next_scene(&self, _rl: &RaylibHandle) -> Box<dyn GameScene> { 
+        if self.retry {
+            let devices = (self.devices.0.get_copy(), self.devices.1.get_copy());
+            let hiscore = SaveData::load().get_highscore(self.game_mode, self.difficulty);
+            return Box::new(GameLoop::new(self.game_mode, self.rules.clone(), devices, hiscore, Settings::load().input));
+        }
+        return Box::new(MainMenu::new()); 
+    }
+}
+
+impl ResultsScene {
+    fn save_entry(self: &mut Self) {
+        let mut save_data = SaveData::load();
+        let entry = HighscoreEntry::new(&self.initials_field.get_initials(), self.stats.best_score, self.stats.best_checkpoint);
+
+        self.rank = save_data.submit(self.game_mode, self.difficulty, entry);
+        save_data.save();
+
+        self.update_table(&save_data.get_table(self.game_mode, self.difficulty));
+        self.retry_btn.enabled = true;
+        self.menu_btn.enabled = true;
+    }
+
+    fn update_table(self: &mut Self, table: &HighscoreTable) {
+        self.table_txts.clear();
+        
+        for (i, entry) in table.entries.iter().enumerate() {
+            let color = if self.rank == Some(i) { Color::GOLD } else { Color::GRAY };
+            let row = format!("{:>2}. {}   {:>4}   {}", i + 1, entry.initials, entry.score, 
+                              MiscUtils::format_date(entry.timestamp));
+
+            self.table_txts.push(Text::new(&row, Vector2::new(0.7, 0.3 + i as f32 * 0.05), color, 16));
+        }
+    }
+
+    fn get_elements(self: &Self, rl: &RaylibHandle) -> ScreenElements {
+        let mut texts = vec![self.title_txt.clone(), self.score_txt.clone()];
+        texts.append(&mut self.stats_txts.clone());
+        let mut fields = vec![];
+
+        if self.initials_field.is_confirmed { texts.append(&mut self.table_txts.clone()); }
+        else {
+            texts.push(self.prompt_txt.clone());
+            fields.push(self.initials_field.get_field());
+        }
+
+        return ScreenElements::new(rl, texts, vec![self.retry_btn.clone(), self.menu_btn.clone()], fields);
+    }
+
+    pub fn new(game_mode: GameMode, difficulty: Difficulty, rules: GameRules, stats: GameStats, 
+               devices: (Box<dyn InputDevice>, Box<dyn InputDevice>)) -> ResultsScene {
+        let table = SaveData::load().get_table(game_mode, difficulty);
+        let rank = table.get_rank(stats.best_score);
+
+        let time_played = stats.time_played as i32;
+        let stats_lines = [
+            format!("Checkpoint: {}", stats.best_checkpoint),
+            format!("Max tier: {}", stats.max_tier + 1),
+            format!("Longest rally: {}", stats.longest_rally),
+            format!("Total bounces: {}", stats.total_bounces),
+            format!("Time played: {:02}:{:02}", time_played / 60, time_played % 60),
+        ];
+
+        let title = match game_mode {
+            GameMode::TimeAttack => "Time's Up",
+            GameMode::Zen => "Practice Over",
+            _ => "Game Over",
+        };
+
+        let mut scene = ResultsScene {
+            game_mode, difficulty, rules, stats, rank, devices,
+            
+            title_txt: Text::new(title, Vector2::new(0.5, 0.08), Color::GOLD, 26),
+            score_txt: Text::new(&format!("Score: {}", stats.best_score), Vector2::new(0.5, 0.17), Color::WHITE, 22),
+            stats_txts: stats_lines.iter().enumerate()
+                .map(|(i, line)| Text::new(line, Vector2::new(0.25, 0.32 + i as f32 * 0.08), Color::GRAY, 18))
+                .collect(),
+            
+            prompt_txt: Text::new("New highscore!\nEnter your initials:", Vector2::new(0.7, 0.32), Color::WHITE, 20),
+            initials_field: InitialsField::new(Vector2::new(0.7, 0.5)),
+            table_txts: vec![],
+            
+            retry_btn: Button::new(rank.is_none(), "Retry", Vector2::new(0.35, 0.88)),
+            menu_btn: Button::new(rank.is_none(), "Main Menu", Vector2::new(0.65, 0.88)),
+            focus: FocusNavigator::new(),
+            is_active: true,
+            retry: false,
+        };
+        
+        // Nothing to type if the score didn't make the table
+        if rank.is_none() {
+            scene.initials_field.is_confirmed = true;
+            scene.update_table(&table);
+        }
+        
+        return scene;
+    }
+}
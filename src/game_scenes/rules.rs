@@ -0,0 +1,137 @@
+use crate::game_scenes::*;
+
+const TIME_ATTACK_LENGTH: f32 = 90.0;
+
+impl BounceModel {
+    pub const ALL: [BounceModel; 2] = [BounceModel::Random, BounceModel::English];
+
+    pub fn get_name(self: &Self) -> &'static str {
+        match self {
+            BounceModel::Random => "Random",
+            BounceModel::English => "English",
+        }
+    }
+}
+
+impl GameMode {
+    // The ones played alone, picked on the title screen
+    pub const SOLO: [GameMode; 4] = [GameMode::Singleplayer, GameMode::TimeAttack, GameMode::Survival, GameMode::Zen];
+
+    pub fn get_name(self: &Self) -> &'static str {
+        match self {
+            GameMode::None => "None",
+            GameMode::Singleplayer => "Singleplayer",
+            GameMode::Multiplayer => "Multiplayer",
+            GameMode::TimeAttack => "Time Attack",
+            GameMode::Survival => "Survival",
+            GameMode::Zen => "Zen",
+        }
+    }
+
+    pub fn get_lives(self: &Self) -> i32 {
+        return if *self == GameMode::Survival { 1 } else { 3 };
+    }
+
+    // Modes without lives just put the ball back
+    pub fn loses_lives(self: &Self) -> bool {
+        return !matches!(self, GameMode::TimeAttack | GameMode::Zen);
+    }
+
+    // Seconds before the game ends on its own
+    pub fn get_time_limit(self: &Self) -> Option<f32> {
+        return if *self == GameMode::TimeAttack { Some(TIME_ATTACK_LENGTH) } else { None };
+    }
+}
+
+impl GameRules {
+    pub fn new() -> GameRules {
+        return GameRules {
+            bounce_model: BounceModel::Random,
+            stage: Stage::get_built_in().remove(0),
+            power_ups: PowerUp::ALL.to_vec(),
+            versus: None,
+        };
+    }
+}
+
+impl MatchFormat {
+    pub const ALL: [MatchFormat; 2] = [MatchFormat::FirstTo, MatchFormat::BestOf];
+
+    pub fn get_name(self: &Self) -> &'static str {
+        match self {
+            MatchFormat::FirstTo => "First to",
+            MatchFormat::BestOf => "Best of",
+        }
+    }
+}
+
+impl MatchRules {
+    pub fn new() -> MatchRules {
+        return MatchRules { format: MatchFormat::FirstTo, rounds: 3, rally_target: 10, swap_sides: true };
+    }
+
+    // Best of ends early once the trailing player can't catch up anymore
+    pub fn is_over(self: &Self, score: &MatchScore) -> bool {
+        let leader = score.points[0].max(score.points[1]);
+        let trailing = score.points[0].min(score.points[1]);
+
+        match self.format {
+            MatchFormat::FirstTo => return leader >= self.rounds,
+            MatchFormat::BestOf => {
+                let rounds_left = self.rounds - score.rounds_played;
+                return rounds_left <= 0 || leader - trailing > rounds_left;
+            }
+        }
+    }
+}
+
+impl MatchScore {
+    // None on a draw, which only happens in an even best of
+    pub fn get_winner(self: &Self) -> Option<usize> {
+        if self.points[0] == self.points[1] { return None; }
+        return Some(if self.points[0] > self.points[1] { 0 } else { 1 });
+    }
+}
+
+// Classic english: the further from the paddle's centre the ball hits, the steeper it leaves,
+// and a moving paddle drags it along. Offset and paddle speed go from -1 to 1
+pub fn get_english_angle(offset: f32, paddle_speed: f32, tunables: &Tunables) -> f32 {
+    let max_angle = tunables.paddle_max_angle;
+    let angle = offset.clamp(-1.0, 1.0) * max_angle + paddle_speed.clamp(-1.0, 1.0) * tunables.english_spin;
+    return angle.clamp(-max_angle, max_angle);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english_follows_the_hit_offset() {
+        let tunables = Tunables::new();
+        assert_eq!(get_english_angle(0.0, 0.0, &tunables), 0.0);
+        assert!(get_english_angle(-0.5, 0.0, &tunables) < 0.0);
+        assert_eq!(get_english_angle(3.0, 0.0, &tunables), tunables.paddle_max_angle);
+    }
+
+    #[test]
+    fn matches_end_when_someone_can_win() {
+        let mut rules = MatchRules::new();
+        let score = |points: [i32; 2]| MatchScore { points, rounds_played: points[0] + points[1], ball_player: 0 };
+        assert!(!rules.is_over(&score([2, 2])));
+        assert!(rules.is_over(&score([3, 1])));
+
+        rules.format = MatchFormat::BestOf;
+        assert!(!rules.is_over(&score([1, 0])));
+        assert!(rules.is_over(&score([2, 0])));
+        assert!(rules.is_over(&score([2, 1])));
+        assert_eq!(score([1, 2]).get_winner(), Some(1));
+    }
+
+    #[test]
+    fn moving_paddles_add_spin() {
+        let tunables = Tunables::new();
+        assert_eq!(get_english_angle(0.0, 1.0, &tunables), tunables.english_spin);
+        assert!(get_english_angle(-0.2, 1.0, &tunables) > get_english_angle(-0.2, 0.0, &tunables));
+        assert_eq!(get_english_angle(1.0, 1.0, &tunables), tunables.paddle_max_angle);
+    }
+}
@@ -0,0 +1,108 @@
+use crate::utils::*;
+use crate::input_system::InputDevice;
+use crate::game_scenes::*;
+
+impl GameLoop {
+    // Gives the round to whoever won it, then starts the next one from zero or ends the match
+    pub fn end_round(self: &mut Self, ball_won: bool) {
+        let Some(versus) = self.rules.versus else { return; };
+        let ball_player = self.match_score.ball_player;
+        let winner = if ball_won { ball_player } else { 1 - ball_player };
+
+        self.match_score.points[winner] += 1;
+        self.match_score.rounds_played += 1;
+        if versus.is_over(&self.match_score) {
+            self.next_scene = SceneType::WinnerScene;
+            self.is_active = false;
+            return;
+        }
+
+        if versus.swap_sides { self.swap_sides(); }
+        self.clear_power_ups();
+        self.left_paddle.position.y = SCREEN_SIZE.y / 2.0 - self.left_paddle.size.y / 2.0;
+        self.right_paddle.position.y = SCREEN_SIZE.y / 2.0 - self.right_paddle.size.y / 2.0;
+
+        self.stats.rally = 0;
+        self.score = 0;
+        self.update_difficulty();
+        self.spawn_ball();
+    }
+
+    // The ball is always player 1's input, so the devices are what change hands
+    fn swap_sides(self: &mut Self) {
+        let ball_device = self.players_input[0].get_device_copy();
+        let paddle_device = self.players_input[1].get_device_copy();
+        self.players_input[0].set_device(paddle_device);
+        self.players_input[1].set_device(ball_device);
+        self.match_score.ball_player = 1 - self.match_score.ball_player;
+    }
+
+    // Devices in the order the players picked them, whoever has the ball right now
+    pub fn get_player_devices(self: &Self) -> (Box<dyn InputDevice>, Box<dyn InputDevice>) {
+        let ball_device = self.players_input[0].get_device_copy();
+        let paddle_device = self.players_input[1].get_device_copy();
+
+        if self.match_score.ball_player == 1 { return (paddle_device, ball_device); }
+        return (ball_device, paddle_device);
+    }
+
+    pub fn get_versus_hud_text(self: &Self, versus: &MatchRules) -> String {
+        let points = self.match_score.points;
+        return format!("P1  {} - {}  P2\nBall: P{}  Rally: {}/{}", points[0], points[1],
+                       self.match_score.ball_player + 1, self.score, versus.rally_target);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input_system::ScriptedInput;
+
+    // Only player 1's device uses its axis, so the sides can be told apart
+    fn new_versus_game(swap_sides: bool) -> GameLoop {
+        let mut rules = GameRules::new();
+        rules.versus = Some(MatchRules { format: MatchFormat::FirstTo, rounds: 2, rally_target: 5, swap_sides });
+        let mut game = GameLoop::new_scripted(GameMode::Multiplayer, rules);
+        game.players_input[0].set_device(Box::new(ScriptedInput::new(true, vec![])));
+        return game;
+    }
+
+    #[test]
+    fn paddle_player_scores_when_the_ball_leaves() {
+        let mut game = new_versus_game(false);
+        game.ball.position.x = -50.0;
+        game.step(1.0, 1.0);
+
+        assert_eq!(game.match_score.points, [0, 1]);
+        assert_eq!(game.ball.lives, 3);
+        assert!(game.ball.is_active);
+    }
+
+    #[test]
+    fn sides_swap_after_each_round() {
+        let mut game = new_versus_game(true);
+        game.score = 5;
+        game.step(1.0, 1.0 / 60.0);
+
+        assert_eq!(game.match_score.points, [1, 0]);
+        assert_eq!(game.score, 0);
+        assert!(!game.players_input[0].get_device_copy().use_axis());
+
+        // Still handed back in the order they were picked
+        let mut devices = game.get_player_devices();
+        assert!(devices.0.use_axis());
+    }
+
+    #[test]
+    fn match_ends_at_the_target() {
+        let mut game = new_versus_game(true);
+        game.end_round(true);
+        assert!(game.is_active);
+
+        // Player 2 has the ball now
+        game.end_round(false);
+        assert!(!game.is_active);
+        assert!(game.next_scene == SceneType::WinnerScene);
+        assert_eq!(game.match_score.get_winner(), Some(0));
+    }
+}
@@ -0,0 +1,59 @@
+use raylib::prelude::*;
+
+use crate::game_scenes::*;
+use crate::settings_system::Settings;
+
+impl GameScene for WinnerScene {
+    fn update(self: &mut Self, rl: &RaylibHandle) {
+        let rows: Vec<Vec<&mut dyn Focusable>> = vec![vec![&mut self.rematch_btn, &mut self.menu_btn]];
+        let command = self.focus.update(rl, rows, &mut [self.devices.0.as_mut(), self.devices.1.as_mut()]);
+
+        if self.rematch_btn.is_pressed(rl) {
+            self.rematch = true;
+            self.is_active = false;
+        }
+        else if self.menu_btn.is_pressed(rl) || command == NavCommand::Back {
+            self.is_active = false;
+        }
+    }
+
+    fn draw(&mut self, canvas: &mut Canvas) {
+        let elements = ScreenElements::new(canvas, vec![self.title_txt.clone(), self.score_txt.clone()],
+                                           vec![self.rematch_btn.clone(), self.menu_btn.clone()], vec![]);
+        canvas.clear_background(Color::BLACK);
+        elements.draw(canvas);
+    }
+
+    fn is_active(&self) -> bool { return self.is_active; }
+    fn get_next_scene(&self, _rl: &RaylibHandle) -> Box<dyn GameScene> {
+        if self.rematch {
+            let devices = (self.devices.0.get_copy(), self.devices.1.get_copy());
+            return Box::new(GameLoop::new(GameMode::Multiplayer, self.rules.clone(), devices, 0, Settings::load().input));
+        }
+        return Box::new(MainMenu::new());
+    }
+}
+
+impl WinnerScene {
+    pub fn new(rules: GameRules, score: MatchScore, devices: (Box<dyn InputDevice>, Box<dyn InputDevice>)) -> WinnerScene {
+        let (title, color) = match score.get_winner() {
+            Some(0) => ("Player 1 Wins", Color::new(10, 255, 255, 255)),
+            Some(_) => ("Player 2 Wins", Color::new(255, 40, 0, 255)),
+            None => ("Draw", Color::GOLD),
+        };
+        let score_line = format!("{} - {}\n\nRounds played: {}", score.points[0], score.points[1], score.rounds_played);
+
+        return WinnerScene {
+            rules, devices,
+
+            title_txt: Text::new(title, Vector2::new(0.5, 0.25), color, 30),
+            score_txt: Text::new(&score_line, Vector2::new(0.5, 0.45), Color::WHITE, 22),
+            rematch_btn: Button::new(true, "Rematch", Vector2::new(0.35, 0.8)),
+            menu_btn: Button::new(true, "Main Menu", Vector2::new(0.65, 0.8)),
+            focus: FocusNavigator::new(),
+
+            is_active: true,
+            rematch: false,
+        };
+    }
+}
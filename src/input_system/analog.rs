@@ -0,0 +1,63 @@
+use super::*;
+
+impl ResponseCurve {
+    pub fn get_name(self: &Self) -> &'static str {
+        match self {
+            ResponseCurve::Linear => "Linear",
+            ResponseCurve::Quadratic => "Quadratic",
+            ResponseCurve::Custom(_) => "Custom",
+        }
+    }
+
+    // Maps a 0..1 tilt to a 0..1 output
+    pub fn apply(self: &Self, value: f32) -> f32 {
+        match *self {
+            ResponseCurve::Linear => return value,
+            ResponseCurve::Quadratic => return value * value,
+            ResponseCurve::Custom(exponent) => return value.powf(exponent),
+        }
+    }
+}
+
+impl Default for AnalogSettings {
+    fn default() -> Self {
+        return AnalogSettings::new();
+    }
+}
+
+impl AnalogSettings {
+    // Rescales the magnitude from the deadzones' range to 0..1 and applies the curve
+    fn apply_magnitude(self: &Self, magnitude: f32) -> f32 {
+        if magnitude < self.inner_deadzone { return 0.0; }
+
+        let range = (self.outer_deadzone - self.inner_deadzone).max(0.01);
+        let value = ((magnitude - self.inner_deadzone) / range).clamp(0.0, 1.0);
+        return self.curve.apply(value);
+    }
+
+    pub fn apply(self: &Self, raw: Vector2) -> Vector2 {
+        match self.deadzone_shape {
+            // Keeps the stick direction, better for free movement
+            DeadzoneShape::Radial => {
+                let magnitude = raw.length();
+                if magnitude == 0.0 { return Vector2::zero(); }
+                return raw * (self.apply_magnitude(magnitude) / magnitude);
+            },
+            // Each axis on its own, snaps near the cardinal directions
+            DeadzoneShape::Axial => {
+                return Vector2::new(raw.x.signum() * self.apply_magnitude(raw.x.abs()),
+                                    raw.y.signum() * self.apply_magnitude(raw.y.abs()));
+            }
+        }
+    }
+
+    pub fn new() -> AnalogSettings {
+        return AnalogSettings {
+            stick: AnalogStick::Left,
+            deadzone_shape: DeadzoneShape::Radial,
+            inner_deadzone: GAMEPAD_DEADZONE,
+            outer_deadzone: 1.0,
+            curve: ResponseCurve::Linear,
+        };
+    }
+}
@@ -0,0 +1,174 @@
+use super::*;
+use raylib::consts::KeyboardKey::*;
+use raylib::consts::GamepadButton::*;
+use raylib::consts::MouseButton::*;
+
+const KEY_NAMES: [(KeyboardKey, &str); 16] = [
+    (KEY_SPACE, "Space"), (KEY_ESCAPE, "Esc"), (KEY_ENTER, "Enter"), (KEY_TAB, "Tab"),
+    (KEY_BACKSPACE, "Backspace"), (KEY_RIGHT, "Right"), (KEY_LEFT, "Left"), (KEY_DOWN, "Down"),
+    (KEY_UP, "Up"), (KEY_LEFT_SHIFT, "L-Shift"), (KEY_RIGHT_SHIFT, "R-Shift"), (KEY_LEFT_CONTROL, "L-Ctrl"),
+    (KEY_RIGHT_CONTROL, "R-Ctrl"), (KEY_LEFT_ALT, "L-Alt"), (KEY_RIGHT_ALT, "R-Alt"), (KEY_KP_ENTER, "Num Enter"),
+];
+
+const MOUSE_BUTTON_NAMES: [(MouseButton, &str); 3] = [
+    (MOUSE_LEFT_BUTTON, "Left Click"), (MOUSE_RIGHT_BUTTON, "Right Click"), (MOUSE_MIDDLE_BUTTON, "Middle Click"),
+];
+
+const BUTTON_NAMES: [(GamepadButton, &str); 17] = [
+    (GAMEPAD_BUTTON_LEFT_FACE_UP, "D-Pad Up"), (GAMEPAD_BUTTON_LEFT_FACE_RIGHT, "D-Pad Right"),
+    (GAMEPAD_BUTTON_LEFT_FACE_DOWN, "D-Pad Down"), (GAMEPAD_BUTTON_LEFT_FACE_LEFT, "D-Pad Left"),
+    (GAMEPAD_BUTTON_RIGHT_FACE_UP, "Y"), (GAMEPAD_BUTTON_RIGHT_FACE_RIGHT, "B"),
+    (GAMEPAD_BUTTON_RIGHT_FACE_DOWN, "A"), (GAMEPAD_BUTTON_RIGHT_FACE_LEFT, "X"),
+    (GAMEPAD_BUTTON_LEFT_TRIGGER_1, "LB"), (GAMEPAD_BUTTON_LEFT_TRIGGER_2, "LT"),
+    (GAMEPAD_BUTTON_RIGHT_TRIGGER_1, "RB"), (GAMEPAD_BUTTON_RIGHT_TRIGGER_2, "RT"),
+    (GAMEPAD_BUTTON_MIDDLE_LEFT, "Select"), (GAMEPAD_BUTTON_MIDDLE, "Home"), (GAMEPAD_BUTTON_MIDDLE_RIGHT, "Start"),
+    (GAMEPAD_BUTTON_LEFT_THUMB, "L-Stick"), (GAMEPAD_BUTTON_RIGHT_THUMB, "R-Stick"),
+];
+
+impl InputAction {
+    pub const ALL: [InputAction; 7] = [InputAction::Up, InputAction::Down, InputAction::Left, InputAction::Right,
+                                       InputAction::Pause, InputAction::Confirm, InputAction::Back];
+
+    pub fn get_name(self: &Self) -> &'static str {
+        match self {
+            InputAction::Up => "Up",
+            InputAction::Down => "Down",
+            InputAction::Left => "Left",
+            InputAction::Right => "Right",
+            InputAction::Pause => "Pause",
+            InputAction::Confirm => "Confirm",
+            InputAction::Back => "Back",
+        }
+    }
+}
+
+impl Binding {
+    pub fn is_down(self: &Self, gamepad_id: i32) -> bool {
+        match *self {
+            Binding::Key(key) => unsafe { ffi::IsKeyDown(key) },
+            Binding::GamepadButton(button) => unsafe { ffi::IsGamepadButtonDown(gamepad_id, button) },
+            Binding::MouseButton(button) => unsafe { ffi::IsMouseButtonDown(button) },
+        }
+    }
+
+    pub fn is_pressed(self: &Self, gamepad_id: i32) -> bool {
+        match *self {
+            Binding::Key(key) => unsafe { ffi::IsKeyPressed(key) },
+            Binding::GamepadButton(button) => unsafe { ffi::IsGamepadButtonPressed(gamepad_id, button) },
+            Binding::MouseButton(button) => unsafe { ffi::IsMouseButtonPressed(button) },
+        }
+    }
+
+    pub fn get_name(self: &Self) -> String {
+        match *self {
+            Binding::Key(key) => {
+                if let Some((_, name)) = KEY_NAMES.iter().find(|(k, _)| *k as i32 == key) {
+                    return name.to_string();
+                }
+
+                // Letters, numbers and symbols are named after themselves
+                match char::from_u32(key as u32) {
+                    Some(c) if c.is_ascii_graphic() => return c.to_string(),
+                    _ => return format!("Key {}", key)
+                }
+            },
+            Binding::GamepadButton(button) => {
+                match BUTTON_NAMES.iter().find(|(b, _)| *b as i32 == button) {
+                    Some((_, name)) => return name.to_string(),
+                    None => return format!("Button {}", button)
+                }
+            },
+            Binding::MouseButton(button) => {
+                match MOUSE_BUTTON_NAMES.iter().find(|(b, _)| *b as i32 == button) {
+                    Some((_, name)) => return name.to_string(),
+                    None => return format!("Mouse {}", button)
+                }
+            }
+        }
+    }
+}
+
+// Only used for whatever a saved profile is missing, devices start from their own defaults
+impl Default for InputProfile {
+    fn default() -> Self {
+        return InputProfile {
+            bindings: Vec::new(),
+            analog: AnalogSettings::new(),
+            gamepad_mode: GamepadMode::Analog,
+            snapness: None,
+            socd_mode: None,
+        };
+    }
+}
+
+impl InputProfile {
+    pub fn get_bindings(self: &Self, action: InputAction) -> Vec<Binding> {
+        return self.bindings.iter().filter(|(a, _)| *a == action).map(|(_, b)| *b).collect();
+    }
+
+    // Replaces every binding the action had
+    pub fn set_binding(self: &mut Self, action: InputAction, binding: Binding) {
+        self.bindings.retain(|(a, _)| *a != action);
+        self.bindings.push((action, binding));
+    }
+
+    pub fn is_action_down(self: &Self, gamepad_id: i32, action: InputAction) -> bool {
+        return self.bindings.iter().any(|(a, b)| *a == action && b.is_down(gamepad_id));
+    }
+
+    pub fn is_action_down_in(self: &Self, state: &GamepadState, action: InputAction) -> bool {
+        return self.bindings.iter().any(|(a, b)| *a == action && match *b {
+            Binding::GamepadButton(button) => state.buttons.get(button as usize).is_some_and(|d| *d),
+            Binding::Key(_) | Binding::MouseButton(_) => false,
+        });
+    }
+
+    pub fn is_action_pressed(self: &Self, gamepad_id: i32, action: InputAction) -> bool {
+        return self.bindings.iter().any(|(a, b)| *a == action && b.is_pressed(gamepad_id));
+    }
+
+    pub fn new_keyboard(use_wasd: bool) -> InputProfile {
+        let keys = if use_wasd { [KEY_W, KEY_S, KEY_A, KEY_D, KEY_ESCAPE, KEY_SPACE, KEY_Q] }
+                   else { [KEY_UP, KEY_DOWN, KEY_LEFT, KEY_RIGHT, KEY_ESCAPE, KEY_ENTER, KEY_BACKSPACE] };
+
+        // Esc goes back in menus too, besides pausing the game
+        let mut bindings: Vec<(InputAction, Binding)> = InputAction::ALL.iter().zip(keys).map(|(a, k)| (*a, Binding::Key(k as i32))).collect();
+        bindings.push((InputAction::Back, Binding::Key(KEY_ESCAPE as i32)));
+
+        return InputProfile {
+            bindings,
+            analog: AnalogSettings::new(),
+            gamepad_mode: GamepadMode::Analog,
+            snapness: None,
+            socd_mode: None,
+        };
+    }
+
+    // Directions come from dragging with the left button, and clicking presses whatever the
+    // pointer is on, so Confirm is left unbound and the other buttons get one action each
+    pub fn new_mouse() -> InputProfile {
+        let buttons = [(InputAction::Pause, MOUSE_MIDDLE_BUTTON), (InputAction::Back, MOUSE_RIGHT_BUTTON)];
+
+        return InputProfile {
+            bindings: buttons.iter().map(|(a, b)| (*a, Binding::MouseButton(*b as i32))).collect(),
+            analog: AnalogSettings::new(),
+            gamepad_mode: GamepadMode::Analog,
+            snapness: None,
+            socd_mode: None,
+        };
+    }
+
+    pub fn new_gamepad() -> InputProfile {
+        let buttons = [GAMEPAD_BUTTON_LEFT_FACE_UP, GAMEPAD_BUTTON_LEFT_FACE_DOWN, GAMEPAD_BUTTON_LEFT_FACE_LEFT,
+                       GAMEPAD_BUTTON_LEFT_FACE_RIGHT, GAMEPAD_BUTTON_MIDDLE_RIGHT, GAMEPAD_BUTTON_RIGHT_FACE_DOWN,
+                       GAMEPAD_BUTTON_RIGHT_FACE_RIGHT];
+
+        return InputProfile {
+            bindings: InputAction::ALL.iter().zip(buttons).map(|(a, b)| (*a, Binding::GamepadButton(b as i32))).collect(),
+            analog: AnalogSettings::new(),
+            gamepad_mode: GamepadMode::Analog,
+            snapness: None,
+            socd_mode: None,
+        };
+    }
+}
@@ -0,0 +1,136 @@
+use std::ffi::CStr;
+
+use super::*;
+use crate::save_system::ProfileData;
+use crate::utils::MAX_CONNECTED_GAMEPADS;
+
+impl DeviceId {
+    pub fn get_name(self: &Self) -> String {
+        match self {
+            DeviceId::Keyboard(true) => return "Keyboard [WASD]".to_string(),
+            DeviceId::Keyboard(false) => return "Keyboard [Arrows]".to_string(),
+            DeviceId::Gamepad(slot, name) if name.is_empty() => return format!("Gamepad {}", slot + 1),
+            DeviceId::Gamepad(slot, name) => return format!("{}: {}", slot + 1, name),
+            DeviceId::Mouse => return "Mouse".to_string(),
+            DeviceId::Scripted => return "Scripted".to_string(),
+        }
+    }
+
+    // Gamepads of the same model share their bindings, whatever slot they're on
+    pub fn get_profile_key(self: &Self) -> String {
+        match self {
+            DeviceId::Gamepad(_, name) if !name.is_empty() => return name.clone(),
+            _ => return self.get_name(),
+        }
+    }
+}
+
+impl DeviceRegistry {
+    // Name of the gamepad plugged on the slot, if any
+    fn read_slot(slot: usize) -> Option<String> {
+        unsafe {
+            if !ffi::IsGamepadAvailable(slot as i32) { return None; }
+            let name = ffi::GetGamepadName(slot as i32);
+            if name.is_null() { return Some(String::new()); }
+            return Some(CStr::from_ptr(name).to_string_lossy().into_owned());
+        }
+    }
+
+    // Should be called every frame, returns what changed since the last call
+    pub fn update(self: &mut Self) -> Vec<DeviceEvent> {
+        let mut events = Vec::new();
+
+        for slot in 0..MAX_CONNECTED_GAMEPADS {
+            let current = Self::read_slot(slot);
+            if current == self.gamepads[slot] { continue; }
+
+            // A different gamepad on the same slot counts as a disconnect and a connect
+            if let Some(name) = self.gamepads[slot].take() {
+                events.push(DeviceEvent::Disconnected(DeviceId::Gamepad(slot as i32, name)));
+            }
+            if let Some(name) = &current {
+                events.push(DeviceEvent::Connected(DeviceId::Gamepad(slot as i32, name.clone())));
+            }
+            self.gamepads[slot] = current;
+        }
+
+        return events;
+    }
+
+    pub fn get_devices(self: &Self) -> Vec<DeviceId> {
+        let mut devices = vec![DeviceId::Keyboard(true), DeviceId::Keyboard(false), DeviceId::Mouse];
+        for (slot, name) in self.gamepads.iter().enumerate() {
+            if let Some(name) = name { devices.push(DeviceId::Gamepad(slot as i32, name.clone())); }
+        }
+        return devices;
+    }
+
+    pub fn is_connected(self: &Self, id: &DeviceId) -> bool {
+        match id {
+            DeviceId::Keyboard(_) | DeviceId::Mouse | DeviceId::Scripted => return true,
+            DeviceId::Gamepad(slot, name) => {
+                return self.gamepads.get(*slot as usize).is_some_and(|n| n.as_ref() == Some(name));
+            }
+        }
+    }
+
+    // A gamepad can come back on another slot, so look for the same model that isn't taken
+    pub fn find_reconnected(self: &Self, id: &DeviceId, taken: &[DeviceId]) -> Option<DeviceId> {
+        if self.is_connected(id) { return Some(id.clone()); }
+
+        let DeviceId::Gamepad(_, name) = id else { return None; };
+        return self.get_devices().into_iter().find(|d| {
+            matches!(d, DeviceId::Gamepad(_, n) if n == name) && !taken.contains(d)
+        });
+    }
+
+    // Step through the connected devices, starting from either end if there's no current one
+    pub fn get_next(self: &Self, current: Option<&DeviceId>, step: i32) -> DeviceId {
+        let devices = self.get_devices();
+        let length = devices.len() as i32;
+
+        let index = match current.and_then(|c| devices.iter().position(|d| d == c)) {
+            Some(i) => (i as i32 + step).rem_euclid(length),
+            None if step > 0 => 0,
+            None => length - 1,
+        };
+        return devices[index as usize].clone();
+    }
+
+    // Creates the device with the bindings saved for it, if any
+    pub fn create_device(id: &DeviceId) -> Box<dyn InputDevice> {
+        return Self::create_device_from(id, &ProfileData::load());
+    }
+
+    // Every connected device, reading the saved profiles only once
+    pub fn create_devices(self: &Self) -> Vec<Box<dyn InputDevice>> {
+        let profiles = ProfileData::load();
+        return self.get_devices().iter().map(|id| Self::create_device_from(id, &profiles)).collect();
+    }
+
+    fn create_device_from(id: &DeviceId, profiles: &ProfileData) -> Box<dyn InputDevice> {
+        let mut device: Box<dyn InputDevice> = match id {
+            DeviceId::Keyboard(use_wasd) => Box::new(KeyboardInput::new(*use_wasd)),
+            DeviceId::Gamepad(slot, name) => Box::new(GamepadInput::new(*slot, name)),
+            DeviceId::Mouse => Box::new(MouseInput::new()),
+            DeviceId::Scripted => Box::new(ScriptedInput::new(false, vec![])),
+        };
+
+        if let Some(profile) = profiles.get_profile(&id.get_profile_key()) {
+            device.set_profile(profile);
+        }
+        return device;
+    }
+
+    // Applies the profile to the device and saves it for the next time it's created
+    pub fn save_profile(device: &mut Box<dyn InputDevice>, profile: InputProfile) {
+        let mut profiles = ProfileData::load();
+        profiles.set_profile(&device.get_id().get_profile_key(), profile.clone());
+        profiles.save();
+        device.set_profile(profile);
+    }
+
+    pub fn new() -> DeviceRegistry {
+        return DeviceRegistry { gamepads: (0..MAX_CONNECTED_GAMEPADS).map(Self::read_slot).collect() };
+    }
+}
@@ -3,45 +3,202 @@ use raylib::consts::GamepadAxis::*;
 use raylib::consts::GamepadButton::*;
 
 impl InputDevice for GamepadInput {
-    fn get_name(self: &mut Self) -> String {
-        return format!("Gamepad {}", self.gamepad_id + 1);
+    fn get_id(self: &Self) -> DeviceId { return DeviceId::Gamepad(self.gamepad_id, self.name.clone()); }
+    fn get_name(self: &mut Self) -> String { return self.get_id().get_name(); }
+
+    fn get_copy(self: &Self) -> Box<dyn InputDevice> { return Box::new(self.clone()); }
+    fn poll(self: &mut Self, _time: f64) {}
+
+    fn get_profile(self: &Self) -> InputProfile { return self.profile.clone(); }
+    fn set_profile(self: &mut Self, profile: InputProfile) { self.profile = profile; }
+    fn get_default_profile(self: &Self) -> InputProfile {
+        return InputProfile::new_gamepad();
+    }
+
+    fn is_action_down(self: &mut Self, action: InputAction) -> bool {
+        return self.profile.is_action_down(self.gamepad_id, action);
+    }
+
+    fn is_action_pressed(self: &mut Self, action: InputAction) -> bool {
+        return self.profile.is_action_pressed(self.gamepad_id, action);
+    }
+
+    fn capture_binding(self: &mut Self) -> Option<Binding> {
+        if unsafe { !ffi::IsGamepadAvailable(self.gamepad_id) } { return None; }
+        let button = (1..=17).find(|b| unsafe { ffi::IsGamepadButtonPressed(self.gamepad_id, *b) })?;
+        return Some(Binding::GamepadButton(button));
     }
 
-    fn use_axis(self: &mut Self) -> bool { return self.use_axis; }
-    fn get_axis(self: &mut Self, rl: &RaylibHandle) -> Vector2 {
-        // Panic if if the device don't have axis or it's set to not use it
-        if !self.use_axis {
+    fn use_axis(self: &mut Self) -> bool { return self.profile.gamepad_mode == GamepadMode::Analog; }
+    fn get_axis(self: &mut Self) -> Vector2 {
+        // Panic if the device is set to not use it
+        if !self.use_axis() {
             panic!("ERROR: Trying to get axis movement data from a input device without axis (Gamepad {})", self.gamepad_id);
         }
         
-        return Vector2::new(rl.get_gamepad_axis_movement(self.gamepad_id, GAMEPAD_AXIS_LEFT_X),
-                            rl.get_gamepad_axis_movement(self.gamepad_id, GAMEPAD_AXIS_LEFT_Y));       
-    }
-
-    fn get_buttons(self: &mut Self, rl: &RaylibHandle) -> [bool; 4]{
-        // Treat left analog as dpad
-        if self.use_axis {
-            let is_right_down = rl.is_gamepad_button_down(self.gamepad_id, GAMEPAD_BUTTON_LEFT_FACE_RIGHT);
-            let is_left_down = rl.is_gamepad_button_down(self.gamepad_id, GAMEPAD_BUTTON_LEFT_FACE_LEFT);
-            let is_down_down = rl.is_gamepad_button_down(self.gamepad_id, GAMEPAD_BUTTON_LEFT_FACE_DOWN);
-            let is_up_down = rl.is_gamepad_button_down(self.gamepad_id, GAMEPAD_BUTTON_LEFT_FACE_UP);
-            return [is_right_down, is_left_down, is_down_down, is_up_down];
-        }
+        let state = self.read_state();
+        return self.get_axis_from(&state);
+    }
 
-        // Use dpads buttons
-        let is_right_down = rl.get_gamepad_axis_movement(self.gamepad_id, GAMEPAD_AXIS_LEFT_X) > GAMEPAD_DEADZONE;
-        let is_left_down = rl.get_gamepad_axis_movement(self.gamepad_id, GAMEPAD_AXIS_LEFT_X) < -GAMEPAD_DEADZONE;
-        let is_down_down = rl.get_gamepad_axis_movement(self.gamepad_id, GAMEPAD_AXIS_LEFT_Y) < -GAMEPAD_DEADZONE;
-        let is_up_down = rl.get_gamepad_axis_movement(self.gamepad_id, GAMEPAD_AXIS_LEFT_Y) > GAMEPAD_DEADZONE;
-        return [is_right_down, is_left_down, is_down_down, is_up_down];
+    fn get_raw_axis(self: &mut Self) -> Vector2 {
+        let state = self.read_state();
+        return self.get_raw_axis_from(&state);
+    }
+
+    fn get_buttons(self: &mut Self) -> [bool; 4]{
+        let state = self.read_state();
+        return self.get_buttons_from(&state);
     }
 }
 
+// Everything below only reads from a GamepadState, so it can be tested without a window
 impl GamepadInput {
-    pub fn new(gamepad_id: i32, use_axis: bool) -> Self {
+    fn read_state(self: &Self) -> GamepadState {
+        let mut state = GamepadState::default();
+        if unsafe { !ffi::IsGamepadAvailable(self.gamepad_id) } { return state; }
+
+        for (i, button) in state.buttons.iter_mut().enumerate() {
+            *button = unsafe { ffi::IsGamepadButtonDown(self.gamepad_id, i as i32) };
+        }
+        for (i, axis) in state.axes.iter_mut().enumerate() {
+            *axis = unsafe { ffi::GetGamepadAxisMovement(self.gamepad_id, i as i32) };
+        }
+        return state;
+    }
+
+    pub fn get_axis_from(self: &Self, state: &GamepadState) -> Vector2 {
+        return self.profile.analog.apply(self.get_raw_axis_from(state));
+    }
+
+    // Selected stick without deadzones or curves applied
+    pub fn get_raw_axis_from(self: &Self, state: &GamepadState) -> Vector2 {
+        let (x_axis, y_axis) = match self.profile.analog.stick {
+            AnalogStick::Left => (GAMEPAD_AXIS_LEFT_X, GAMEPAD_AXIS_LEFT_Y),
+            AnalogStick::Right => (GAMEPAD_AXIS_RIGHT_X, GAMEPAD_AXIS_RIGHT_Y),
+            AnalogStick::DPad => {
+                let buttons = [GAMEPAD_BUTTON_LEFT_FACE_RIGHT, GAMEPAD_BUTTON_LEFT_FACE_LEFT, 
+                               GAMEPAD_BUTTON_LEFT_FACE_DOWN, GAMEPAD_BUTTON_LEFT_FACE_UP]
+                               .map(|b| state.buttons[b as usize] as i32 as f32);
+                return Vector2::new(buttons[0] - buttons[1], buttons[2] - buttons[3]);
+            }
+        };
+
+        return Vector2::new(state.axes[x_axis as usize], state.axes[y_axis as usize]);
+    }
+
+    // Right, left, down and up
+    pub fn get_buttons_from(self: &Self, state: &GamepadState) -> [bool; 4] {
+        if self.profile.gamepad_mode == GamepadMode::DPad {
+            return [InputAction::Right, InputAction::Left, InputAction::Down, InputAction::Up]
+                   .map(|a| self.profile.is_action_down_in(state, a));
+        }
+
+        // Stick tilted past the deadzone, raylib's y axis points down
+        let raw = self.get_raw_axis_from(state);
+        let deadzone = self.profile.analog.inner_deadzone;
+        return [raw.x > deadzone, raw.x < -deadzone, raw.y > deadzone, raw.y < -deadzone];
+    }
+
+    pub fn new(gamepad_id: i32, name: &str) -> Self {
         return Self {
-            use_axis: use_axis,
             gamepad_id: gamepad_id,
+            name: name.to_string(),
+            profile: InputProfile::new_gamepad(),
         };
     }
 }
+
+impl GamepadMode {
+    pub const ALL: [GamepadMode; 3] = [GamepadMode::Analog, GamepadMode::DPad, GamepadMode::StickDigital];
+
+    pub fn get_name(self: &Self) -> &'static str {
+        match self {
+            GamepadMode::Analog => "Analog Stick",
+            GamepadMode::DPad => "D-Pad",
+            GamepadMode::StickDigital => "Stick as D-Pad",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A gamepad that's never plugged in, all input comes from the state given to it
+    fn fake_gamepad(mode: GamepadMode) -> GamepadInput {
+        let mut gamepad = GamepadInput::new(0, "Fake Gamepad");
+        gamepad.profile.gamepad_mode = mode;
+        return gamepad;
+    }
+
+    fn with_stick(x: f32, y: f32) -> GamepadState {
+        let mut state = GamepadState::default();
+        state.axes[GAMEPAD_AXIS_LEFT_X as usize] = x;
+        state.axes[GAMEPAD_AXIS_LEFT_Y as usize] = y;
+        return state;
+    }
+
+    fn with_buttons(buttons: &[GamepadButton]) -> GamepadState {
+        let mut state = GamepadState::default();
+        for button in buttons { state.buttons[*button as usize] = true; }
+        return state;
+    }
+
+    #[test]
+    fn stick_up_presses_up() {
+        let mut gamepad = fake_gamepad(GamepadMode::StickDigital);
+        assert_eq!(gamepad.get_buttons_from(&with_stick(0.0, -1.0)), [false, false, false, true]);
+        assert_eq!(gamepad.get_buttons_from(&with_stick(0.0, 1.0)), [false, false, true, false]);
+        assert_eq!(gamepad.get_buttons_from(&with_stick(1.0, 0.0)), [true, false, false, false]);
+        assert_eq!(gamepad.get_buttons_from(&with_stick(-1.0, 0.0)), [false, true, false, false]);
+        assert!(!gamepad.use_axis());
+    }
+
+    #[test]
+    fn stick_inside_deadzone_presses_nothing() {
+        let gamepad = fake_gamepad(GamepadMode::StickDigital);
+        let tilt = GAMEPAD_DEADZONE * 0.5;
+        assert_eq!(gamepad.get_buttons_from(&with_stick(tilt, -tilt)), [false; 4]);
+    }
+
+    #[test]
+    fn dpad_mode_ignores_stick() {
+        let mut gamepad = fake_gamepad(GamepadMode::DPad);
+        let mut state = with_buttons(&[GAMEPAD_BUTTON_LEFT_FACE_UP, GAMEPAD_BUTTON_LEFT_FACE_LEFT]);
+        state.axes[GAMEPAD_AXIS_LEFT_X as usize] = 1.0;
+
+        assert_eq!(gamepad.get_buttons_from(&state), [false, true, false, true]);
+        assert!(!gamepad.use_axis());
+    }
+
+    #[test]
+    fn dpad_mode_follows_bindings() {
+        let mut gamepad = fake_gamepad(GamepadMode::DPad);
+        gamepad.profile.set_binding(InputAction::Up, Binding::GamepadButton(GAMEPAD_BUTTON_RIGHT_FACE_UP as i32));
+
+        assert_eq!(gamepad.get_buttons_from(&with_buttons(&[GAMEPAD_BUTTON_RIGHT_FACE_UP])), [false, false, false, true]);
+        assert_eq!(gamepad.get_buttons_from(&with_buttons(&[GAMEPAD_BUTTON_LEFT_FACE_UP])), [false; 4]);
+    }
+
+    #[test]
+    fn analog_mode_applies_deadzone() {
+        let mut gamepad = fake_gamepad(GamepadMode::Analog);
+        assert!(gamepad.use_axis());
+        assert_eq!(gamepad.get_axis_from(&with_stick(GAMEPAD_DEADZONE * 0.5, 0.0)), Vector2::zero());
+        assert_eq!(gamepad.get_axis_from(&with_stick(0.0, -1.0)), Vector2::new(0.0, -1.0));
+    }
+
+    #[test]
+    fn reads_selected_stick() {
+        let mut gamepad = fake_gamepad(GamepadMode::Analog);
+        gamepad.profile.analog.stick = AnalogStick::Right;
+
+        let mut state = with_stick(1.0, 0.0);
+        state.axes[GAMEPAD_AXIS_RIGHT_Y as usize] = 0.5;
+        assert_eq!(gamepad.get_raw_axis_from(&state), Vector2::new(0.0, 0.5));
+
+        gamepad.profile.analog.stick = AnalogStick::DPad;
+        let state = with_buttons(&[GAMEPAD_BUTTON_LEFT_FACE_RIGHT, GAMEPAD_BUTTON_LEFT_FACE_DOWN]);
+        assert_eq!(gamepad.get_raw_axis_from(&state), Vector2::new(1.0, 1.0));
+    }
+}
@@ -1,39 +1,58 @@
 use super::*;
-use raylib::consts::KeyboardKey::*;
 
 impl InputDevice for KeyboardInput {
-    fn get_name(self: &mut Self) -> String {
-        if self.use_wasd {
-            return "Keyboard [WASD]".to_string();
-        }
-        else {
-            return "Keyboard [Arrows]".to_string();
-        }
-    }
+    fn get_id(self: &Self) -> DeviceId { return DeviceId::Keyboard(self.use_wasd); }
+    fn get_name(self: &mut Self) -> String { return self.get_id().get_name(); }
     
-    // A keyboard don't have axis
-    fn use_axis(self: &mut Self) -> bool { return false; }
-    fn get_axis(self: &mut Self, _rl: &RaylibHandle) -> Vector2 {
-        panic!("ERROR: Trying to get axis movement data from a input device without axis (Keyboard)");      
+    fn get_copy(self: &Self) -> Box<dyn InputDevice> { return Box::new(self.clone()); }
+    fn poll(self: &mut Self, _time: f64) {}
+
+    fn get_profile(self: &Self) -> InputProfile { return self.profile.clone(); }
+    fn set_profile(self: &mut Self, profile: InputProfile) { self.profile = profile; }
+    fn get_default_profile(self: &Self) -> InputProfile {
+        return InputProfile::new_keyboard(self.use_wasd);
+    }
+
+    fn is_action_down(self: &mut Self, action: InputAction) -> bool {
+        return self.profile.is_action_down(-1, action);
+    }
+
+    fn is_action_pressed(self: &mut Self, action: InputAction) -> bool {
+        return self.profile.is_action_pressed(-1, action);
     }
 
-    fn get_buttons(self: &mut Self, rl: &RaylibHandle) -> [bool; 4] {
-        // Use WASD
-        if self.use_wasd {
-            return [rl.is_key_down(KEY_D), rl.is_key_down(KEY_A),
-                    rl.is_key_down(KEY_S), rl.is_key_down(KEY_W)
-            ]
-        }
-
-        // Use arrows
-        return [rl.is_key_down(KEY_RIGHT), rl.is_key_down(KEY_LEFT),
-                rl.is_key_down(KEY_DOWN), rl.is_key_down(KEY_UP)
+    fn capture_binding(self: &mut Self) -> Option<Binding> {
+        let key = unsafe { ffi::GetKeyPressed() };
+        if key <= 0 { return None; }
+        return Some(Binding::Key(key));
+    }
+
+    // A keyboard don't have axis, so it always reads as centered
+    fn use_axis(self: &mut Self) -> bool { return false; }
+    fn get_axis(self: &mut Self) -> Vector2 { return Vector2::zero(); }
+    fn get_raw_axis(self: &mut Self) -> Vector2 { return Vector2::zero(); }
+
+    fn get_buttons(self: &mut Self) -> [bool; 4] {
+        return [self.is_action_down(InputAction::Right), self.is_action_down(InputAction::Left),
+                self.is_action_down(InputAction::Down), self.is_action_down(InputAction::Up)
         ]
     }
 }
 
 impl KeyboardInput {
     pub fn new(use_wasd: bool) -> Self {
-        return Self { use_wasd };
+        return Self { use_wasd, profile: InputProfile::new_keyboard(use_wasd) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn axis_reads_as_centered() {
+        let mut keyboard = KeyboardInput::new(true);
+        assert_eq!(keyboard.get_axis(), Vector2::zero());
+        assert_eq!(keyboard.get_raw_axis(), Vector2::zero());
     }
 }
@@ -1,9 +1,19 @@
 mod keyboard_input;
+mod mouse_input;
 mod gamepad_input;
 mod player_input;
+mod bindings;
+mod device_registry;
+mod analog;
+mod socd;
+mod scripted_input;
 use raylib::prelude::*;
+use serde::{Serialize, Deserialize};
 
 const GAMEPAD_DEADZONE: f32 = 0.15;
+const GAMEPAD_BUTTONS: usize = 18; // Raylib's button codes, including the unknown one
+const GAMEPAD_AXES: usize = 6;
+const MOUSE_DRAG_RADIUS: f32 = 60.0; // Drag distance in pixels that tilts fully
 
 #[derive(Clone, Copy)]
 pub struct InputData {
@@ -14,7 +24,8 @@ pub struct InputData {
     
     pub sample_time: f64,
     pub raw_dir: Vector2,
-    pub dir: Vector2
+    pub dir: Vector2,
+    pub socd_mode: Option<SocdMode>, // What resolved the buttons into raw_dir, None for axis devices
 }
 
 impl InputData {
@@ -26,17 +37,103 @@ impl InputData {
             is_left_down: false, 
             is_down_down: false, 
             is_up_down: false,
-            sample_time
+            sample_time,
+            socd_mode: None,
         }
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum InputAction { Up, Down, Left, Right, Pause, Confirm, Back }
+
+// Raw raylib key and button codes, so they can be saved to disk
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Binding { Key(i32), GamepadButton(i32), MouseButton(i32) }
+
+// How opposing directions held at the same time are resolved
+// (https://www.hitboxarcade.com/blogs/support/what-is-socd)
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SocdMode {
+    FirstInput,     // The key held for longer wins
+    LastInput,      // The key pressed last wins
+    Neutral,        // Both cancel out
+    UpPriority,     // Up wins vertically, left and right cancel out
+}
+
+// How a gamepad moves the player
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum GamepadMode {
+    Analog,         // Stick with deadzones and curves applied
+    DPad,           // Directional buttons
+    StickDigital,   // Stick read as four buttons, with SOCD cleaning
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AnalogStick { Left, Right, DPad }
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DeadzoneShape { Radial, Axial }
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ResponseCurve { Linear, Quadratic, Custom(f32) } // Custom uses its value as the exponent
+
+// Saved as TOML, anything missing from the file gets its default
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnalogSettings {
+    pub stick: AnalogStick,
+    pub deadzone_shape: DeadzoneShape,
+    pub inner_deadzone: f32, // Below this the stick reads as centered
+    pub outer_deadzone: f32, // Above this the stick reads as fully tilted
+    pub curve: ResponseCurve,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputProfile {
+    pub bindings: Vec<(InputAction, Binding)>,
+    pub analog: AnalogSettings,
+    pub gamepad_mode: GamepadMode,
+    pub snapness: Option<f32>, // Overrides the player's default input smoothing
+    pub socd_mode: Option<SocdMode>, // Overrides the player's default SOCD resolution
+}
+
+// Stable identity of a device, so it can be found again after being unplugged
+#[derive(Clone, PartialEq)]
+pub enum DeviceId {
+    Keyboard(bool),         // Uses WASD
+    Gamepad(i32, String),   // Slot and gamepad name
+    Mouse,                  // Also driven by touch and trackpads
+    Scripted,               // Plays back a ScriptedInput timeline
+}
+
+pub enum DeviceEvent { Connected(DeviceId), Disconnected(DeviceId) }
+
+// Tracks which gamepads are plugged in on each slot
+pub struct DeviceRegistry {
+    gamepads: Vec<Option<String>>,
+}
+
+// Devices read raylib's input state directly, so they don't need a RaylibHandle
+// and can be replaced by a ScriptedInput where there's no window
 pub trait InputDevice {
-    fn get_buttons(self: &mut Self, rl: &RaylibHandle) -> [bool; 4];
-    fn get_axis(self: &mut Self, rl: &RaylibHandle) -> Vector2;
+    fn poll(self: &mut Self, time: f64); // Called once per frame before reading it
+    fn get_buttons(self: &mut Self) -> [bool; 4];
+    fn get_axis(self: &mut Self) -> Vector2;
+    fn get_raw_axis(self: &mut Self) -> Vector2;
+    
+    fn is_action_down(self: &mut Self, action: InputAction) -> bool;
+    fn is_action_pressed(self: &mut Self, action: InputAction) -> bool;
+    fn capture_binding(self: &mut Self) -> Option<Binding>;
+
+    fn get_profile(self: &Self) -> InputProfile;
+    fn set_profile(self: &mut Self, profile: InputProfile);
+    fn get_default_profile(self: &Self) -> InputProfile;
     
+    fn get_id(self: &Self) -> DeviceId;
     fn get_name(self: &mut Self) -> String;
     fn use_axis(self: &mut Self) -> bool;
+    fn get_copy(self: &Self) -> Box<dyn InputDevice>;
 }
 
 pub struct PlayerInput {
@@ -44,17 +141,51 @@ pub struct PlayerInput {
     input_snapness: f32,
     last_data: InputData,
 
-    first_input_socd: bool,
+    socd_mode: SocdMode,
+    press_times: [Option<f64>; 4], // When each held direction was pressed
     device: Box<dyn InputDevice>,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub struct KeyboardInput {
-    use_wasd: bool
+    use_wasd: bool,
+    profile: InputProfile,
 }
 
-#[derive(Clone, Copy)]
+// Dragging with the left button works like a stick, centered where the drag started
+#[derive(Clone)]
+pub struct MouseInput {
+    drag_start: Option<Vector2>,
+    profile: InputProfile,
+}
+
+// Buttons and axes of a gamepad on a single frame
+#[derive(Clone, Copy, Default)]
+pub struct GamepadState {
+    pub buttons: [bool; GAMEPAD_BUTTONS],
+    pub axes: [f32; GAMEPAD_AXES],
+}
+
+#[derive(Clone)]
 pub struct GamepadInput {
     gamepad_id: i32,
-    use_axis: bool
+    name: String,
+    profile: InputProfile,
+}
+
+// What a ScriptedInput holds from the time it starts until the next frame
+#[derive(Clone)]
+pub struct ScriptedFrame {
+    pub held: Vec<InputAction>,
+    pub axis: Vector2,
+}
+
+// Plays back a timeline of inputs, so players can be driven without a window
+#[derive(Clone)]
+pub struct ScriptedInput {
+    timeline: Vec<(f64, ScriptedFrame)>, // Start time of each frame, in order
+    use_axis: bool,
+    current: ScriptedFrame,
+    previous: ScriptedFrame,
+    profile: InputProfile,
 }
\ No newline at end of file
@@ -0,0 +1,103 @@
+use super::*;
+use raylib::consts::MouseButton::*;
+
+impl InputDevice for MouseInput {
+    fn get_id(self: &Self) -> DeviceId { return DeviceId::Mouse; }
+    fn get_name(self: &mut Self) -> String { return self.get_id().get_name(); }
+
+    fn get_copy(self: &Self) -> Box<dyn InputDevice> { return Box::new(self.clone()); }
+
+    // Starts a drag where the button was pressed, and ends it once it's released
+    fn poll(self: &mut Self, _time: f64) {
+        let button = MOUSE_LEFT_BUTTON as i32;
+        if unsafe { !ffi::IsMouseButtonDown(button) } { self.drag_start = None; }
+        else if self.drag_start.is_none() { self.drag_start = Some(Self::get_mouse_position()); }
+    }
+
+    fn get_profile(self: &Self) -> InputProfile { return self.profile.clone(); }
+    fn set_profile(self: &mut Self, profile: InputProfile) { self.profile = profile; }
+    fn get_default_profile(self: &Self) -> InputProfile {
+        return InputProfile::new_mouse();
+    }
+
+    fn is_action_down(self: &mut Self, action: InputAction) -> bool {
+        return self.profile.is_action_down(-1, action);
+    }
+
+    fn is_action_pressed(self: &mut Self, action: InputAction) -> bool {
+        return self.profile.is_action_pressed(-1, action);
+    }
+
+    // The left button is kept for dragging
+    fn capture_binding(self: &mut Self) -> Option<Binding> {
+        let buttons = [MOUSE_RIGHT_BUTTON, MOUSE_MIDDLE_BUTTON];
+        let button = buttons.iter().find(|b| unsafe { ffi::IsMouseButtonPressed(**b as i32) })?;
+        return Some(Binding::MouseButton(*button as i32));
+    }
+
+    fn use_axis(self: &mut Self) -> bool { return true; }
+    fn get_axis(self: &mut Self) -> Vector2 {
+        let raw = self.get_raw_axis();
+        return self.profile.analog.apply(raw);
+    }
+
+    fn get_raw_axis(self: &mut Self) -> Vector2 {
+        if self.drag_start.is_none() { return Vector2::zero(); }
+        return self.get_raw_axis_from(Self::get_mouse_position());
+    }
+
+    fn get_buttons(self: &mut Self) -> [bool; 4] {
+        let axis = self.get_axis();
+        return [axis.x > 0.0, axis.x < 0.0, axis.y > 0.0, axis.y < 0.0];
+    }
+}
+
+impl MouseInput {
+    fn get_mouse_position() -> Vector2 {
+        return unsafe { ffi::GetMousePosition() }.into();
+    }
+
+    // Drag vector to the pointer, reaching full tilt at MOUSE_DRAG_RADIUS
+    pub fn get_raw_axis_from(self: &Self, position: Vector2) -> Vector2 {
+        let Some(start) = self.drag_start else { return Vector2::zero(); };
+        let drag = (position - start) / MOUSE_DRAG_RADIUS;
+        return if drag.length() > 1.0 { drag.normalized() } else { drag };
+    }
+
+    pub fn new() -> Self {
+        return Self { drag_start: None, profile: InputProfile::new_mouse() };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dragging_from(start: Vector2) -> MouseInput {
+        let mut input = MouseInput::new();
+        input.drag_start = Some(start);
+        return input;
+    }
+
+    #[test]
+    fn drag_tilts_up_to_the_radius() {
+        let input = dragging_from(Vector2::new(100.0, 100.0));
+        assert_eq!(input.get_raw_axis_from(Vector2::new(100.0 + MOUSE_DRAG_RADIUS / 2.0, 100.0)), Vector2::new(0.5, 0.0));
+        assert_eq!(input.get_raw_axis_from(Vector2::new(100.0, 100.0 - MOUSE_DRAG_RADIUS * 3.0)), Vector2::new(0.0, -1.0));
+    }
+
+    #[test]
+    fn no_drag_is_centered() {
+        let input = MouseInput::new();
+        assert_eq!(input.get_raw_axis_from(Vector2::new(300.0, 50.0)), Vector2::zero());
+    }
+
+    #[test]
+    fn actions_dont_share_buttons() {
+        let profile = MouseInput::new().get_default_profile();
+        let buttons: Vec<Binding> = profile.bindings.iter().map(|(_, b)| *b).collect();
+
+        assert!(!buttons.contains(&Binding::MouseButton(MOUSE_LEFT_BUTTON as i32)));
+        assert!(buttons.iter().enumerate().all(|(i, b)| !buttons[..i].contains(b)));
+    }
+}
@@ -5,80 +5,152 @@ impl PlayerInput {
         self.last_data.dir = new_dir;
     }
     
-    pub fn get_data(self: &mut Self, rl: &RaylibHandle) -> InputData {
+    // Input from the last time it was sampled
+    pub fn get_last_data(self: &Self) -> InputData {
+        return self.last_data;
+    }
+
+    pub fn get_device_id(self: &Self) -> DeviceId {
+        return self.device.get_id();
+    }
+
+    pub fn set_device(self: &mut Self, device: Box<dyn InputDevice>) {
+        self.device = device;
+    }
+
+    // The device's profile can override the player's smoothing
+    fn get_snapness(self: &Self) -> f32 {
+        return self.device.get_profile().snapness.unwrap_or(self.input_snapness);
+    }
+
+    // The device's profile can override the player's SOCD resolution too
+    fn get_socd_mode(self: &Self) -> SocdMode {
+        return self.device.get_profile().socd_mode.unwrap_or(self.socd_mode);
+    }
+
+    // Smoothing and SOCD resolution used when the device's profile doesn't override them
+    pub fn set_defaults(self: &mut Self, snapness: f32, socd_mode: SocdMode) {
+        self.input_snapness = snapness;
+        self.socd_mode = socd_mode;
+    }
+
+    pub fn get_device(self: &mut Self) -> &mut dyn InputDevice {
+        return self.device.as_mut();
+    }
+
+    pub fn get_device_copy(self: &Self) -> Box<dyn InputDevice> {
+        return self.device.get_copy();
+    }
+
+    pub fn is_action_pressed(self: &mut Self, action: InputAction) -> bool {
+        return self.device.is_action_pressed(action);
+    }
+
+    // Frame timing is given instead of read from raylib, so it can run without a window
+    pub fn sample(self: &mut Self, time: f64, frame_time: f32) -> InputData {
         // Update data if wasn't already this frame
-        if self.last_data.sample_time != rl.get_time() {
-            self.last_data = self.read_data(&rl)
+        if self.last_data.sample_time != time {
+            self.device.poll(time);
+            self.last_data = self.read_data(time, frame_time);
         }
         
         return self.last_data;
     }
 
-    fn read_data(self: &mut Self, rl: &RaylibHandle) -> InputData {
-        let mut data = InputData::new(rl.get_time());
+    fn read_data(self: &mut Self, time: f64, frame_time: f32) -> InputData {
+        let mut data = InputData::new(time);
         let previous_dir = self.last_data.dir;
 
         // Get buttons
-        let buttons = self.device.get_buttons(&rl);
+        let buttons = self.device.get_buttons();
         data.is_right_down = buttons[0];
         data.is_left_down = buttons[1];
         data.is_down_down = buttons[2];
         data.is_up_down = buttons[3];
 
         // Get raw direction
-        if self.device.use_axis() { data.raw_dir = self.device.get_axis(&rl); }
-        else { data.raw_dir = self.buttons_to_dir(&buttons, &previous_dir); }
+        if self.device.use_axis() { data.raw_dir = self.device.get_axis(); }
+        else {
+            data.raw_dir = self.buttons_to_dir(&buttons, data.sample_time);
+            data.socd_mode = Some(self.get_socd_mode());
+        }
 
         // Smooth raw direction to dir
-        data.dir = previous_dir.lerp(data.raw_dir, self.input_snapness * rl.get_frame_time());
+        data.dir = previous_dir.lerp(data.raw_dir, self.get_snapness() * frame_time);
         return data;
     }
 
     // Convert buttons to a direction vector, while applying SOCD cleaning
-    // (https://www.hitboxarcade.com/blogs/support/what-is-socd)
-    fn buttons_to_dir(self: &mut Self, raw_data: &[bool; 4], previous_dir: &Vector2) -> Vector2 {
-        let mut dir = Vector2::zero();
-        let is_right_down = raw_data[0];
-        let is_left_down = raw_data[1];
-        let is_down_down = raw_data[2];
-        let is_up_down = raw_data[3];
-
-        // Use First-Input Priority resolution
-        if self.first_input_socd {
-            if !is_right_down && !is_left_down { dir.x = 0.0; }
-            else if is_right_down && !is_left_down { dir.x = 1.0; }
-            else if !is_right_down { dir.x = -1.0; }
-            else { dir.x = previous_dir.x }
-
-            if !is_down_down && !is_up_down { dir.y = 0.0; }
-            else if is_down_down && !is_up_down { dir.y = 1.0; }
-            else if !is_down_down {dir.y = -1.0; }
-            else { dir.y = previous_dir.y }
-
-        }
-        // Use Neutral resolution 
-        else {
-            if is_left_down && is_right_down { dir.x = 0.0; }
-            else if is_left_down { dir.x = -1.0}
-            else if is_right_down { dir.x = 1.0}
-            else { dir.x = 0.0 }
-
-            if is_up_down && is_down_down { dir.y = 0.0; }
-            else if is_up_down { dir.y = -1.0}
-            else if is_down_down { dir.y = 1.0}
-            else { dir.y = 0.0 }
+    fn buttons_to_dir(self: &mut Self, raw_data: &[bool; 4], time: f64) -> Vector2 {
+        // Keep the time each direction started being held
+        for (press_time, is_down) in self.press_times.iter_mut().zip(raw_data) {
+            if !is_down { *press_time = None; }
+            else if press_time.is_none() { *press_time = Some(time); }
         }
 
-        return dir;
+        let mode = self.get_socd_mode();
+        let [right, left, down, up] = self.press_times;
+        return Vector2::new(mode.resolve(left, right, false), mode.resolve(up, down, true));
     }
 
-    pub fn new(player_id: i32, device: Box<dyn InputDevice>, snapness: f32, use_first_input: bool) -> Self {
+    pub fn new(player_id: i32, device: Box<dyn InputDevice>, snapness: f32, socd_mode: SocdMode) -> Self {
         return Self { 
             id: player_id, 
             input_snapness: snapness, 
             last_data: InputData::new(0.0), 
-            first_input_socd: use_first_input, 
+            socd_mode: socd_mode,
+            press_times: [None; 4],
             device: device
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_player(timeline: Vec<(f64, ScriptedFrame)>) -> PlayerInput {
+        return PlayerInput::new(0, Box::new(ScriptedInput::new(false, timeline)), 3.0, SocdMode::LastInput);
+    }
+
+    #[test]
+    fn buttons_are_smoothed_towards_their_direction() {
+        let mut player = new_player(vec![(0.0, ScriptedFrame::new(&[InputAction::Right], Vector2::zero()))]);
+
+        let first = player.sample(0.1, 0.1);
+        assert_eq!(first.raw_dir, Vector2::new(1.0, 0.0));
+        assert!(first.dir.x > 0.0 && first.dir.x < 1.0);
+
+        let second = player.sample(0.2, 0.1);
+        assert!(second.dir.x > first.dir.x);
+    }
+
+    #[test]
+    fn same_time_is_only_sampled_once() {
+        let mut player = new_player(vec![(0.0, ScriptedFrame::new(&[InputAction::Up], Vector2::zero()))]);
+
+        let first = player.sample(0.1, 0.1);
+        let second = player.sample(0.1, 0.1);
+        assert_eq!(first.dir, second.dir);
+    }
+
+    #[test]
+    fn opposing_buttons_use_the_socd_mode() {
+        let mut player = new_player(vec![
+            (0.0, ScriptedFrame::new(&[InputAction::Left], Vector2::zero())),
+            (1.0, ScriptedFrame::new(&[InputAction::Left, InputAction::Right], Vector2::zero())),
+        ]);
+
+        assert_eq!(player.sample(0.5, 0.1).raw_dir.x, -1.0);
+        assert_eq!(player.sample(1.5, 0.1).raw_dir.x, 1.0);
+    }
+
+    #[test]
+    fn axis_devices_skip_the_buttons() {
+        let script = ScriptedInput::new(true, vec![(0.0, ScriptedFrame::new(&[InputAction::Left], Vector2::new(0.3, 0.2)))]);
+        let mut player = PlayerInput::new(0, Box::new(script), 3.0, SocdMode::Neutral);
+
+        let data = player.sample(0.1, 0.1);
+        assert_eq!(data.raw_dir, Vector2::new(0.3, 0.2));
+        assert!(data.is_left_down);
+    }
+}
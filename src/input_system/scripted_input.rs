@@ -0,0 +1,108 @@
+use super::*;
+
+impl InputDevice for ScriptedInput {
+    fn get_id(self: &Self) -> DeviceId { return DeviceId::Scripted; }
+    fn get_name(self: &mut Self) -> String { return self.get_id().get_name(); }
+
+    fn get_copy(self: &Self) -> Box<dyn InputDevice> { return Box::new(self.clone()); }
+
+    // Moves to the last frame that already started, nothing is held before the first one
+    fn poll(self: &mut Self, time: f64) {
+        let frame = self.timeline.iter().rev().find(|(start, _)| *start <= time);
+        let current = frame.map_or(ScriptedFrame::new(&[], Vector2::zero()), |(_, f)| f.clone());
+        self.previous = std::mem::replace(&mut self.current, current);
+    }
+
+    fn get_profile(self: &Self) -> InputProfile { return self.profile.clone(); }
+    fn set_profile(self: &mut Self, profile: InputProfile) { self.profile = profile; }
+    fn get_default_profile(self: &Self) -> InputProfile {
+        let mut profile = InputProfile::new_gamepad();
+        profile.gamepad_mode = if self.use_axis { GamepadMode::Analog } else { GamepadMode::DPad };
+        return profile;
+    }
+
+    fn is_action_down(self: &mut Self, action: InputAction) -> bool {
+        return self.current.held.contains(&action);
+    }
+
+    // Held on this poll, but not on the one before
+    fn is_action_pressed(self: &mut Self, action: InputAction) -> bool {
+        return self.current.held.contains(&action) && !self.previous.held.contains(&action);
+    }
+
+    fn capture_binding(self: &mut Self) -> Option<Binding> { return None; }
+
+    fn use_axis(self: &mut Self) -> bool { return self.use_axis; }
+    fn get_axis(self: &mut Self) -> Vector2 { return self.current.axis; }
+    fn get_raw_axis(self: &mut Self) -> Vector2 { return self.current.axis; }
+
+    fn get_buttons(self: &mut Self) -> [bool; 4] {
+        return [self.is_action_down(InputAction::Right), self.is_action_down(InputAction::Left),
+                self.is_action_down(InputAction::Down), self.is_action_down(InputAction::Up)
+        ]
+    }
+}
+
+impl ScriptedFrame {
+    pub fn new(held: &[InputAction], axis: Vector2) -> Self {
+        return Self { held: held.to_vec(), axis };
+    }
+}
+
+impl ScriptedInput {
+    // Frames are given with the time they start at, and are sorted by it
+    pub fn new(use_axis: bool, mut timeline: Vec<(f64, ScriptedFrame)>) -> Self {
+        timeline.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut input = Self {
+            timeline,
+            use_axis,
+            current: ScriptedFrame::new(&[], Vector2::zero()),
+            previous: ScriptedFrame::new(&[], Vector2::zero()),
+            profile: InputProfile::new_gamepad(),
+        };
+        input.profile = input.get_default_profile();
+        return input;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn script() -> ScriptedInput {
+        return ScriptedInput::new(true, vec![
+            (1.0, ScriptedFrame::new(&[InputAction::Up], Vector2::new(0.0, -1.0))),
+            (0.0, ScriptedFrame::new(&[], Vector2::new(0.5, 0.0))),
+            (2.0, ScriptedFrame::new(&[InputAction::Up, InputAction::Pause], Vector2::zero())),
+        ]);
+    }
+
+    #[test]
+    fn plays_back_the_frame_that_started_last() {
+        let mut input = script();
+
+        input.poll(-1.0);
+        assert_eq!(input.get_axis(), Vector2::zero());
+        input.poll(0.5);
+        assert_eq!(input.get_axis(), Vector2::new(0.5, 0.0));
+        input.poll(1.0);
+        assert_eq!(input.get_axis(), Vector2::new(0.0, -1.0));
+        assert_eq!(input.get_buttons(), [false, false, false, true]);
+        input.poll(10.0);
+        assert!(input.is_action_down(InputAction::Pause));
+    }
+
+    #[test]
+    fn pressed_only_on_the_first_poll_held() {
+        let mut input = script();
+
+        input.poll(1.0);
+        assert!(input.is_action_pressed(InputAction::Up));
+        input.poll(1.5);
+        assert!(!input.is_action_pressed(InputAction::Up));
+        input.poll(2.0);
+        assert!(!input.is_action_pressed(InputAction::Up));
+        assert!(input.is_action_pressed(InputAction::Pause));
+    }
+}
@@ -0,0 +1,83 @@
+use super::*;
+
+impl SocdMode {
+    pub const ALL: [SocdMode; 4] = [SocdMode::FirstInput, SocdMode::LastInput, SocdMode::Neutral, SocdMode::UpPriority];
+
+    pub fn get_name(self: &Self) -> &'static str {
+        match self {
+            SocdMode::FirstInput => "First Input",
+            SocdMode::LastInput => "Last Input",
+            SocdMode::Neutral => "Neutral",
+            SocdMode::UpPriority => "Up Priority",
+        }
+    }
+
+    // Resolves one axis from the time each of its keys was pressed, if they're held.
+    // Negative is left or up, keys pressed on the same frame cancel out
+    pub fn resolve(self: &Self, negative: Option<f64>, positive: Option<f64>, is_vertical: bool) -> f32 {
+        let (negative_time, positive_time) = match (negative, positive) {
+            (None, None) => return 0.0,
+            (Some(_), None) => return -1.0,
+            (None, Some(_)) => return 1.0,
+            (Some(n), Some(p)) => (n, p),
+        };
+
+        match self {
+            SocdMode::Neutral => return 0.0,
+            SocdMode::UpPriority if is_vertical => return -1.0,
+            SocdMode::UpPriority => return 0.0,
+            SocdMode::FirstInput if negative_time < positive_time => return -1.0,
+            SocdMode::FirstInput if positive_time < negative_time => return 1.0,
+            SocdMode::LastInput if negative_time > positive_time => return -1.0,
+            SocdMode::LastInput if positive_time > negative_time => return 1.0,
+            _ => return 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_key_always_wins() {
+        for mode in SocdMode::ALL {
+            assert_eq!(mode.resolve(Some(1.0), None, false), -1.0);
+            assert_eq!(mode.resolve(None, Some(1.0), true), 1.0);
+            assert_eq!(mode.resolve(None, None, true), 0.0);
+        }
+    }
+
+    #[test]
+    fn first_input_keeps_oldest_key() {
+        assert_eq!(SocdMode::FirstInput.resolve(Some(1.0), Some(2.0), false), -1.0);
+        assert_eq!(SocdMode::FirstInput.resolve(Some(2.0), Some(1.0), false), 1.0);
+        assert_eq!(SocdMode::FirstInput.resolve(Some(1.0), Some(2.0), true), -1.0);
+    }
+
+    #[test]
+    fn last_input_takes_newest_key() {
+        assert_eq!(SocdMode::LastInput.resolve(Some(1.0), Some(2.0), false), 1.0);
+        assert_eq!(SocdMode::LastInput.resolve(Some(2.0), Some(1.0), false), -1.0);
+        assert_eq!(SocdMode::LastInput.resolve(Some(2.0), Some(1.0), true), -1.0);
+    }
+
+    #[test]
+    fn neutral_cancels_out() {
+        assert_eq!(SocdMode::Neutral.resolve(Some(1.0), Some(2.0), false), 0.0);
+        assert_eq!(SocdMode::Neutral.resolve(Some(2.0), Some(1.0), true), 0.0);
+    }
+
+    #[test]
+    fn up_priority_wins_vertically_only() {
+        assert_eq!(SocdMode::UpPriority.resolve(Some(2.0), Some(1.0), true), -1.0);
+        assert_eq!(SocdMode::UpPriority.resolve(Some(1.0), Some(2.0), true), -1.0);
+        assert_eq!(SocdMode::UpPriority.resolve(Some(1.0), Some(2.0), false), 0.0);
+    }
+
+    #[test]
+    fn same_frame_presses_cancel_out() {
+        assert_eq!(SocdMode::FirstInput.resolve(Some(1.0), Some(1.0), false), 0.0);
+        assert_eq!(SocdMode::LastInput.resolve(Some(1.0), Some(1.0), false), 0.0);
+    }
+}
@@ -1,18 +1,31 @@
 mod ui_system;
 mod input_system;
 mod game_objects;
+mod collision_system;
+mod stage_system;
+mod render_system;
+mod audio_system;
 mod game_scenes;
 mod networking;
+mod save_system;
+mod settings_system;
 mod utils;
 
 use game_scenes::*;
 use utils::*;
+use render_system::Renderer;
+use audio_system::AudioPlayer;
+use settings_system::Settings;
 
 
 fn main() {
-    if DebugUtils::is_debug_session() { DebugUtils::debug() }   
+    let settings = Settings::load();
+    if settings.debug || DebugUtils::is_debug_session() { DebugUtils::debug() }   
       
-    let (mut rl, thread) = MiscUtils::init_window();
+    let video_settings = settings.video;
+    let (mut rl, thread) = MiscUtils::init_window(&video_settings);
+    let mut renderer = Renderer::new(&mut rl, &thread, video_settings);
+    let mut audio = AudioPlayer::new(&thread);
     let mut scene: Box<dyn GameScene> = Box::new(MainMenu::new());
 
     // Each frame
@@ -21,7 +34,9 @@ fn main() {
             scene = scene.get_next_scene(&rl); 
         }
 
+        renderer.update(&mut rl);
         scene.update(&rl);
-        scene.draw(&mut rl, &thread);
+        audio.update(scene.get_music_level());
+        renderer.draw(&mut rl, &thread, scene.as_mut());
     }
 }
\ No newline at end of file
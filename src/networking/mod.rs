@@ -1,6 +1,6 @@
 use std::net::UdpSocket;
 use std::time::Duration;
-use bincode::serialized_size;
+use bincode::{serialize, serialized_size};
 use serde::{Serialize, Deserialize};
 
 #[derive(Serialize, Deserialize, PartialEq, Debug)]
@@ -58,24 +58,42 @@ impl<T: Serialize + 'static> NetworkMessage<T> {
 }
 
 pub struct NetworkManager {
-    remote_addr: String
+    remote_addr: String,
+    port: u16,
 }
 
 impl NetworkManager {
-    pub fn new(remote: String) -> NetworkManager {
-        return Self { remote_addr: remote+":26655" };
+    pub fn new(remote: String, port: u16) -> NetworkManager {
+        return Self { remote_addr: format!("{}:{}", remote, port), port };
     }
     
     pub fn punch_hole(self: &mut Self) {
-        let socket = UdpSocket::bind("0.0.0.0:26655").unwrap();
+        let socket = UdpSocket::bind(("0.0.0.0", self.port)).unwrap();
         socket.set_write_timeout(Some(Duration::from_secs(1))).unwrap();
 
         let result = socket.send_to(&[1;10], self.remote_addr.clone()).expect("Sending failed -> ");
         println!("Message sended (code {})", result);
     }
 
+    // Lets the peer know the game was paused or resumed, so it can freeze along with it.
+    // Nothing reads it on the other side yet, that comes with the online sessions
+    pub fn send_pause(self: &mut Self, is_paused: bool) {
+        let socket = match UdpSocket::bind(("0.0.0.0", self.port)) {
+            Ok(s) => s,
+            Err(e) => {
+                println!("Couldn't open a socket to send the pause: {}", e);
+                return;
+            }
+        };
+
+        let bytes = serialize(&NetworkMessage::new(is_paused)).expect("Couldn't serialize pause message");
+        if let Err(e) = socket.send_to(&bytes, self.remote_addr.clone()) {
+            println!("Couldn't send the pause to '{}': {}", self.remote_addr, e);
+        }
+    }
+
     pub fn listen(self: &mut Self) {
-        let socket = UdpSocket::bind("0.0.0.0:26655").unwrap();
+        let socket = UdpSocket::bind(("0.0.0.0", self.port)).unwrap();
         let result = socket.set_read_timeout(Some(Duration::from_secs(1))).unwrap();
 
         let mut msg = &mut [0 as u8; 4];
@@ -0,0 +1,16 @@
+mod renderer;
+
+use raylib::prelude::*;
+
+use crate::settings_system::VideoSettings;
+
+// What scenes draw on, at SCREEN_SIZE no matter how big the window is
+pub type Canvas<'a, 'b> = RaylibTextureMode<'a, RaylibDrawHandle<'b>>;
+
+// Draws the game to a texture and scales it to fit the window, with black bars to keep its aspect ratio
+pub struct Renderer {
+    target: RenderTexture2D,
+    viewport: Rectangle,    // Where the game ends up in the window
+    settings: VideoSettings,
+    revision: u32,          // Of the settings last applied
+}
@@ -0,0 +1,113 @@
+use raylib::prelude::*;
+use raylib::consts::KeyboardKey::*;
+
+use super::*;
+use crate::utils::*;
+use crate::game_scenes::GameScene;
+use crate::settings_system::Settings;
+
+impl Renderer {
+    pub fn new(rl: &mut RaylibHandle, thread: &RaylibThread, settings: VideoSettings) -> Renderer {
+        let target = rl.load_render_texture(thread, SCREEN_SIZE.x as u32, SCREEN_SIZE.y as u32)
+            .expect("Couldn't create the render texture");
+
+        let mut renderer = Renderer { target, viewport: Self::get_viewport(SCREEN_SIZE), settings, revision: Settings::get_revision() };
+        renderer.apply_settings(rl, settings);
+        return renderer;
+    }
+
+    // As big as the window allows, centred
+    pub fn get_viewport(window_size: Vector2) -> Rectangle {
+        let scale = (window_size.x / SCREEN_SIZE.x).min(window_size.y / SCREEN_SIZE.y);
+        let size = SCREEN_SIZE * scale;
+        return Rectangle::new((window_size.x - size.x) / 2.0, (window_size.y - size.y) / 2.0, size.x, size.y);
+    }
+
+    // Goes before the scene updates, so it already reads the mouse in game coordinates
+    pub fn update(self: &mut Self, rl: &mut RaylibHandle) {
+        if Settings::get_revision() != self.revision {
+            self.revision = Settings::get_revision();
+            self.apply_settings(rl, Settings::load().video);
+        }
+
+        if rl.is_key_pressed(KEY_F11) {
+            self.settings.fullscreen = !self.settings.fullscreen;
+            self.set_fullscreen(rl);
+            self.save_settings();
+        }
+
+        // Dragging the window's borders picks the windowed resolution
+        if rl.is_window_resized() && !rl.is_window_fullscreen() {
+            self.settings.resolution = (rl.get_screen_width(), rl.get_screen_height());
+            self.save_settings();
+        }
+
+        self.viewport = Self::get_viewport(Vector2::new(rl.get_screen_width() as f32, rl.get_screen_height() as f32));
+        let scale = SCREEN_SIZE.x / self.viewport.width;
+        rl.set_mouse_offset(Vector2::new(-self.viewport.x, -self.viewport.y));
+        rl.set_mouse_scale(scale, scale);
+    }
+
+    // Only changes what's different from the window's current state
+    fn apply_settings(self: &mut Self, rl: &mut RaylibHandle, settings: VideoSettings) {
+        let previous = self.settings;
+        self.settings = settings;
+
+        if rl.is_window_fullscreen() != settings.fullscreen { self.set_fullscreen(rl); }
+        else if !settings.fullscreen && settings.resolution != previous.resolution {
+            rl.set_window_size(settings.resolution.0, settings.resolution.1);
+        }
+
+        let vsync = WindowState::default().set_vsync_hint(true);
+        if settings.vsync { rl.set_window_state(vsync); } else { rl.clear_window_state(vsync); }
+        rl.set_target_fps(settings.fps_limit);
+    }
+
+    // Fullscreen takes the monitor's resolution, and leaving it goes back to the saved one
+    fn set_fullscreen(self: &Self, rl: &mut RaylibHandle) {
+        if rl.is_window_fullscreen() == self.settings.fullscreen { return; }
+
+        if self.settings.fullscreen {
+            let monitor = get_current_monitor();
+            rl.set_window_size(get_monitor_width(monitor), get_monitor_height(monitor));
+            rl.toggle_fullscreen();
+        }
+        else {
+            rl.toggle_fullscreen();
+            rl.set_window_size(self.settings.resolution.0, self.settings.resolution.1);
+        }
+    }
+
+    // Keeps the revision, since these changes are already applied
+    fn save_settings(self: &mut Self) {
+        let mut settings = Settings::load();
+        settings.video = self.settings;
+        settings.save();
+        self.revision = Settings::get_revision();
+    }
+
+    pub fn draw(self: &mut Self, rl: &mut RaylibHandle, thread: &RaylibThread, scene: &mut dyn GameScene) {
+        let mut draw_handle = rl.begin_drawing(thread);
+        draw_handle.clear_background(Color::BLACK);
+
+        let mut canvas = draw_handle.begin_texture_mode(thread, self.target.as_mut());
+        scene.draw(&mut canvas);
+        drop(canvas);
+
+        // Render textures are stored upside down
+        let source = Rectangle::new(0.0, 0.0, SCREEN_SIZE.x, -SCREEN_SIZE.y);
+        draw_handle.draw_texture_pro(&self.target, source, self.viewport, Vector2::zero(), 0.0, Color::WHITE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn viewport_keeps_the_aspect_ratio() {
+        assert_eq!(Renderer::get_viewport(SCREEN_SIZE), Rectangle::new(0.0, 0.0, 640.0, 480.0));
+        assert_eq!(Renderer::get_viewport(Vector2::new(1920.0, 1080.0)), Rectangle::new(240.0, 0.0, 1440.0, 1080.0));
+        assert_eq!(Renderer::get_viewport(Vector2::new(640.0, 960.0)), Rectangle::new(0.0, 240.0, 640.0, 480.0));
+    }
+}
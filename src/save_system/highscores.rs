@@ -0,0 +1,103 @@
+use std::time::UNIX_EPOCH;
+
+use super::*;
+
+impl HighscoreEntry {
+    pub fn new(initials: &str, score: i32, checkpoint: i32) -> Self {
+        return Self {
+            initials: initials.to_string(),
+            timestamp: MiscUtils::get_timestamp(),
+            score, checkpoint,
+        };
+    }
+}
+
+impl HighscoreTable {
+    fn new(game_mode: GameMode, difficulty: Difficulty) -> Self {
+        return Self { game_mode, difficulty, entries: Vec::with_capacity(MAX_TABLE_ENTRIES) };
+    }
+
+    pub fn get_best_score(self: &Self) -> i32 {
+        return self.entries.first().map_or(0, |e| e.score);
+    }
+
+    // Position the score would take on the table, if it makes it
+    pub fn get_rank(self: &Self, score: i32) -> Option<usize> {
+        if score <= 0 { return None; }
+
+        // Ties go below older entries
+        let rank = self.entries.iter().position(|e| score > e.score).unwrap_or(self.entries.len());
+        if rank >= MAX_TABLE_ENTRIES { return None; }
+        return Some(rank);
+    }
+
+    fn insert(self: &mut Self, entry: HighscoreEntry) -> Option<usize> {
+        let rank = self.get_rank(entry.score)?;
+        self.entries.insert(rank, entry);
+        self.entries.truncate(MAX_TABLE_ENTRIES);
+        return Some(rank);
+    }
+}
+
+impl SaveData {
+    pub fn load() -> SaveData {
+        return load_file(SAVE_FILE).unwrap_or_else(Self::migrate_legacy);
+    }
+
+    pub fn save(self: &Self) {
+        save_file(SAVE_FILE, self);
+    }
+
+    pub fn get_table(self: &Self, game_mode: GameMode, difficulty: Difficulty) -> HighscoreTable {
+        return match self.tables.iter().find(|t| t.game_mode == game_mode && t.difficulty == difficulty) {
+            Some(table) => table.clone(),
+            None => HighscoreTable::new(game_mode, difficulty)
+        };
+    }
+
+    pub fn get_highscore(self: &Self, game_mode: GameMode, difficulty: Difficulty) -> i32 {
+        return self.get_table(game_mode, difficulty).get_best_score();
+    }
+
+    // Adds the entry to its table and returns the rank it got, if any
+    pub fn submit(self: &mut Self, game_mode: GameMode, difficulty: Difficulty, entry: HighscoreEntry) -> Option<usize> {
+        let index = match self.tables.iter().position(|t| t.game_mode == game_mode && t.difficulty == difficulty) {
+            Some(i) => i,
+            None => {
+                self.tables.push(HighscoreTable::new(game_mode, difficulty));
+                self.tables.len() - 1
+            }
+        };
+
+        return self.tables[index].insert(entry);
+    }
+
+    // Imports the single score from the old 'highscore.txt' format
+    fn migrate_legacy() -> SaveData {
+        let mut data = SaveData::default();
+        let legacy = match fs::read_to_string(LEGACY_HIGHSCORE_FILE) {
+            Ok(s) => s,
+            Err(_) => return data,
+        };
+
+        // Older versions didn't truncate the file when saving, so ignore anything after the number
+        let digits: String = legacy.trim().chars().take_while(|c| c.is_ascii_digit()).collect();
+        let score = match digits.parse::<i32>() {
+            Ok(s) => s,
+            Err(_) => {
+                println!("Couldn't read score from '{}', skipping migration...", LEGACY_HIGHSCORE_FILE);
+                return data;
+            }
+        };
+
+        let mut entry = HighscoreEntry::new("---", score, 0);
+        if let Ok(modified) = fs::metadata(LEGACY_HIGHSCORE_FILE).and_then(|m| m.modified()) {
+            entry.timestamp = modified.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        }
+
+        println!("Migrating highscore {} from '{}'...", score, LEGACY_HIGHSCORE_FILE);
+        data.submit(GameMode::Singleplayer, Difficulty::Normal, entry);
+        data.save();
+        return data;
+    }
+}
\ No newline at end of file
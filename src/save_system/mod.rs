@@ -0,0 +1,102 @@
+mod highscores;
+mod profiles;
+mod power_ups;
+
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::AtomicU32;
+use std::collections::BTreeMap;
+use bincode::{deserialize, serialize};
+use serde::{Serialize, Deserialize};
+use serde::de::DeserializeOwned;
+
+use crate::utils::*;
+use crate::input_system::InputProfile;
+use crate::game_objects::PowerUp;
+use crate::game_scenes::{GameMode, Difficulty};
+
+pub const MAX_TABLE_ENTRIES: usize = 10;
+const SAVE_FILE: &str = "save.bin";
+const PROFILES_FILE: &str = "profiles.toml";
+const POWER_UPS_FILE: &str = "power_ups.toml";
+const LEGACY_HIGHSCORE_FILE: &str = "highscore.txt";
+
+// Goes up every time the profiles are saved, so devices created from them can tell they're outdated
+static PROFILES_REVISION: AtomicU32 = AtomicU32::new(0);
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct HighscoreEntry {
+    pub initials: String,
+    pub score: i32,
+    pub checkpoint: i32,
+    pub timestamp: u64, // Seconds since UNIX epoch
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct HighscoreTable {
+    pub game_mode: GameMode,
+    pub difficulty: Difficulty,
+    pub entries: Vec<HighscoreEntry>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct SaveData {
+    tables: Vec<HighscoreTable>,
+}
+
+// Input profiles, by device name. Kept as TOML like the settings, so older files
+// still load after new fields are added to the profiles
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ProfileData {
+    profiles: BTreeMap<String, InputProfile>,
+}
+
+// Power-ups turned on for each game mode, all of them if the mode isn't here. Kept as TOML too
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PowerUpData {
+    modes: Vec<(GameMode, Vec<PowerUp>)>,
+}
+
+// Returns None if the file doesn't exist yet. Files ending in '.toml' are read as TOML, the rest with bincode
+fn load_file<T: DeserializeOwned + Default>(file_name: &str) -> Option<T> {
+    return load_from(&MiscUtils::get_data_dir().join(file_name));
+}
+
+fn load_from<T: DeserializeOwned + Default>(path: &Path) -> Option<T> {
+    let bytes = fs::read(path).ok()?;
+    let extension = path.extension()?.to_string_lossy().into_owned();
+
+    let result = match extension.as_str() {
+        "toml" => String::from_utf8(bytes).map_err(|e| e.to_string())
+                      .and_then(|text| toml::from_str::<T>(&text).map_err(|e| e.to_string())),
+        _ => deserialize::<T>(&bytes).map_err(|e| e.to_string()),
+    };
+
+    match result {
+        Ok(data) => return Some(data),
+        Err(e) => {
+            // Keep the broken file around instead of overwriting it on the next save
+            println!("File '{}' is corrupted ({}), starting a new one...", path.display(), e);
+            let _ = fs::rename(path, path.with_extension(format!("{}.bak", extension)));
+            return Some(T::default());
+        }
+    }
+}
+
+fn save_file<T: Serialize>(file_name: &str, data: &T) {
+    save_to(&MiscUtils::get_data_dir().join(file_name), data);
+}
+
+fn save_to<T: Serialize>(path: &Path, data: &T) {
+    let bytes = if path.extension().is_some_and(|e| e == "toml") {
+        toml::to_string_pretty(data).expect("Couldn't serialize save data").into_bytes()
+    } else {
+        serialize(data).expect("Couldn't serialize save data")
+    };
+
+    if let Err(e) = MiscUtils::write_atomically(path, &bytes) {
+        println!("Couldn't write file '{}': {}", path.display(), e);
+    }
+}
@@ -0,0 +1,40 @@
+use super::*;
+
+impl PowerUpData {
+    pub fn load() -> PowerUpData {
+        return load_file(POWER_UPS_FILE).unwrap_or_default();
+    }
+
+    pub fn save(self: &Self) {
+        save_file(POWER_UPS_FILE, self);
+    }
+
+    pub fn get_enabled(self: &Self, game_mode: GameMode) -> Vec<PowerUp> {
+        return self.modes.iter().find(|(mode, _)| *mode == game_mode).map_or(PowerUp::ALL.to_vec(), |(_, p)| p.clone());
+    }
+
+    pub fn set_enabled(self: &mut Self, game_mode: GameMode, power_ups: Vec<PowerUp>) {
+        self.modes.retain(|(mode, _)| *mode != game_mode);
+        self.modes.push((game_mode, power_ups));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saved_power_ups_load_back() {
+        let dir = std::env::temp_dir().join(format!("pong_2_power_ups_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(POWER_UPS_FILE);
+
+        let mut data = PowerUpData::default();
+        data.set_enabled(GameMode::Multiplayer, vec![PowerUp::Shrink, PowerUp::PaddleSlow]);
+        save_to(&path, &data);
+
+        let loaded = load_from::<PowerUpData>(&path).unwrap();
+        assert!(loaded.get_enabled(GameMode::Multiplayer) == vec![PowerUp::Shrink, PowerUp::PaddleSlow]);
+        assert!(loaded.get_enabled(GameMode::Singleplayer) == PowerUp::ALL.to_vec());
+    }
+}
@@ -0,0 +1,66 @@
+use std::sync::atomic::Ordering;
+
+use super::*;
+
+impl ProfileData {
+    pub fn load() -> ProfileData {
+        return load_file(PROFILES_FILE).unwrap_or_default();
+    }
+
+    pub fn save(self: &Self) {
+        save_file(PROFILES_FILE, self);
+        PROFILES_REVISION.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn get_revision() -> u32 {
+        return PROFILES_REVISION.load(Ordering::Relaxed);
+    }
+
+    pub fn get_profile(self: &Self, device_name: &str) -> Option<InputProfile> {
+        return self.profiles.get(device_name).cloned();
+    }
+
+    pub fn set_profile(self: &mut Self, device_name: &str, profile: InputProfile) {
+        self.profiles.insert(device_name.to_string(), profile);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input_system::{AnalogSettings, Binding, InputAction, ResponseCurve};
+
+    // A file of its own in the temp dir, so tests never touch the real profiles
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("pong_2_profiles_{}_{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        return dir.join(PROFILES_FILE);
+    }
+
+    #[test]
+    fn saved_profiles_load_back() {
+        let path = temp_path("round_trip");
+        let mut profile = InputProfile::new_gamepad();
+        profile.analog.curve = ResponseCurve::Custom(1.5);
+        profile.snapness = Some(2.0);
+
+        let mut profiles = ProfileData::default();
+        profiles.set_profile("Gamepad 1", profile);
+        save_to(&path, &profiles);
+
+        let loaded = load_from::<ProfileData>(&path).unwrap().get_profile("Gamepad 1").unwrap();
+        assert!(loaded.bindings == InputProfile::new_gamepad().bindings);
+        assert!(loaded.analog.curve == ResponseCurve::Custom(1.5));
+        assert_eq!(loaded.snapness, Some(2.0));
+    }
+
+    #[test]
+    fn missing_fields_get_their_defaults() {
+        let path = temp_path("partial");
+        fs::write(&path, "[profiles.Mouse]\nbindings = [[\"Back\", { MouseButton = 1 }]]\n").unwrap();
+
+        let loaded = load_from::<ProfileData>(&path).unwrap().get_profile("Mouse").unwrap();
+        assert!(loaded.bindings == vec![(InputAction::Back, Binding::MouseButton(1))]);
+        assert!(loaded.analog == AnalogSettings::new());
+    }
+}
@@ -0,0 +1,60 @@
+mod settings;
+
+use std::sync::atomic::AtomicU32;
+use serde::{Serialize, Deserialize};
+
+use crate::input_system::SocdMode;
+
+const SETTINGS_FILE: &str = "settings.toml";
+
+// Goes up on every save, so whatever applies the settings can tell they changed
+static REVISION: AtomicU32 = AtomicU32::new(0);
+
+// Window sizes to pick from, any other one can be set by resizing the window
+pub const RESOLUTIONS: [(i32, i32); 5] = [(640, 480), (960, 720), (1280, 720), (1280, 960), (1920, 1080)];
+pub const FPS_LIMITS: [u32; 5] = [30, 60, 120, 144, 0]; // 0 is unlimited
+
+// Kept in the config dir as TOML so players can edit it, unlike saves which go in the data dir.
+// Anything missing from the file gets its default, so older files still load after new settings are added
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(default)]
+pub struct Settings {
+    pub debug: bool, // Runs the debug code on start, DEBUG=1 turns it on too
+    pub video: VideoSettings,
+    pub audio: AudioSettings,
+    pub input: InputSettings,
+    pub network: NetworkSettings,
+}
+
+// The game itself is always drawn at SCREEN_SIZE
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct VideoSettings {
+    pub resolution: (i32, i32),     // Window size when not fullscreen
+    pub fullscreen: bool,
+    pub vsync: bool,
+    pub fps_limit: u32,
+}
+
+// Volumes go from 0 to 1, music and effects are scaled by the master volume
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub effects_volume: f32,
+}
+
+// Each player's defaults, which device profiles can override
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct InputSettings {
+    pub snapness: [f32; 2],
+    pub socd_modes: [SocdMode; 2],
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct NetworkSettings {
+    pub port: u16,
+}
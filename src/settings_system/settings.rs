@@ -0,0 +1,120 @@
+use std::fs;
+use std::sync::atomic::Ordering;
+use std::path::Path;
+
+use super::*;
+use crate::utils::*;
+
+impl Default for VideoSettings {
+    fn default() -> Self {
+        return VideoSettings { resolution: RESOLUTIONS[0], fullscreen: false, vsync: true, fps_limit: 60 };
+    }
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        return AudioSettings { master_volume: 0.8, music_volume: 0.6, effects_volume: 1.0 };
+    }
+}
+
+impl Default for InputSettings {
+    fn default() -> Self {
+        return InputSettings { snapness: [3.0, 7.0], socd_modes: [SocdMode::FirstInput, SocdMode::Neutral] };
+    }
+}
+
+impl Default for NetworkSettings {
+    fn default() -> Self {
+        return NetworkSettings { port: 26655 };
+    }
+}
+
+impl Settings {
+    pub fn load() -> Settings {
+        return Self::load_from(&MiscUtils::get_config_dir().join(SETTINGS_FILE));
+    }
+
+    // Defaults if there's no file yet, or if it can't be read
+    fn load_from(path: &Path) -> Settings {
+        let Ok(text) = fs::read_to_string(path) else { return Settings::default(); };
+
+        match toml::from_str::<Settings>(&text) {
+            Ok(settings) => return settings,
+            Err(e) => {
+                // Keep the broken file around instead of overwriting it on the next save
+                println!("Settings file '{}' is corrupted ({}), using the defaults...", path.display(), e);
+                let _ = fs::rename(path, path.with_extension("toml.bak"));
+                return Settings::default();
+            }
+        }
+    }
+
+    pub fn save(self: &Self) {
+        self.save_to(&MiscUtils::get_config_dir().join(SETTINGS_FILE));
+        REVISION.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn save_to(self: &Self, path: &Path) {
+        let text = toml::to_string_pretty(self).expect("Couldn't serialize settings");
+
+        if let Err(e) = MiscUtils::write_atomically(path, text.as_bytes()) {
+            println!("Couldn't write settings '{}': {}", path.display(), e);
+        }
+    }
+
+    pub fn get_revision() -> u32 {
+        return REVISION.load(Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A file of its own in the temp dir, so tests never touch the real settings
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("pong_2_settings_{}_{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        return dir.join(SETTINGS_FILE);
+    }
+
+    #[test]
+    fn missing_file_loads_the_defaults() {
+        let path = temp_path("missing");
+        let _ = fs::remove_file(&path);
+        assert!(Settings::load_from(&path) == Settings::default());
+    }
+
+    #[test]
+    fn corrupt_file_is_kept_aside() {
+        let path = temp_path("corrupt");
+        fs::write(&path, "video = [not toml").unwrap();
+
+        assert!(Settings::load_from(&path) == Settings::default());
+        assert!(!path.exists());
+        assert!(path.with_extension("toml.bak").exists());
+    }
+
+    #[test]
+    fn missing_values_get_their_defaults() {
+        let path = temp_path("partial");
+        fs::write(&path, "[audio]\nmusic_volume = 0.25\n").unwrap();
+
+        let settings = Settings::load_from(&path);
+        assert_eq!(settings.audio.music_volume, 0.25);
+        assert_eq!(settings.audio.master_volume, AudioSettings::default().master_volume);
+        assert!(settings.video == VideoSettings::default());
+    }
+
+    #[test]
+    fn saved_settings_load_back() {
+        let path = temp_path("round_trip");
+        let mut settings = Settings::default();
+        settings.video.resolution = RESOLUTIONS[2];
+        settings.input.socd_modes[1] = SocdMode::LastInput;
+        settings.debug = true;
+
+        settings.save_to(&path);
+        assert!(Settings::load_from(&path) == settings);
+    }
+}
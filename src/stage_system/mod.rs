@@ -0,0 +1,40 @@
+mod stage;
+mod parser;
+
+use raylib::prelude::*;
+
+const STAGES_DIR: &str = "stages"; // Custom stages, inside the data dir
+const STAGE_EXTENSION: &str = "stage";
+
+// Shipped with the game, the first one is the default
+const BUILT_IN_STAGES: [&str; 6] = [
+    include_str!("../../stages/classic.stage"),
+    include_str!("../../stages/pillars.stage"),
+    include_str!("../../stages/pinball.stage"),
+    include_str!("../../stages/warp.stage"),
+    include_str!("../../stages/currents.stage"),
+    include_str!("../../stages/crusher.stage"),
+];
+
+// An arena layout. Stage files have one object per line, with positions and sizes as fractions
+// of the screen, and '#' starting a comment. They're in pixels once loaded
+#[derive(Clone)]
+pub struct Stage {
+    pub name: String,               // name <text>
+    pub paddle_padding: f32,        // paddles <distance from the sides>
+    pub objects: Vec<StageObject>,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum StageObject {
+    // wall <x> <y> <width> <height>
+    Wall(Rectangle),
+    // mover <x> <y> <width> <height> <offset x> <offset y> <seconds>, goes to the offset and back
+    Mover { rect: Rectangle, offset: Vector2, period: f32 },
+    // bumper <x> <y> <radius> <kick>, the kick is extra speed that wears off
+    Bumper { center: Vector2, radius: f32, kick: f32 },
+    // portal <x> <y> <exit x> <exit y> <radius>, works both ways
+    Portal { entry: Vector2, exit: Vector2, radius: f32 },
+    // zone <x> <y> <width> <height> <speed>, scales the ball's speed inside it
+    Zone { rect: Rectangle, speed: f32 },
+}
@@ -0,0 +1,62 @@
+use super::*;
+use crate::utils::*;
+use crate::game_objects::PADDLE_PADDING;
+
+impl Stage {
+    pub fn new(name: &str) -> Stage {
+        return Stage { name: name.to_string(), paddle_padding: PADDLE_PADDING, objects: Vec::new() };
+    }
+
+    // Errors say which line is wrong, so stage files are easy to fix
+    pub fn parse(text: &str) -> Result<Stage, String> {
+        let mut stage = Stage::new("Unnamed");
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            let (keyword, args) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            if keyword.is_empty() { continue; }
+
+            if keyword == "name" {
+                stage.name = args.trim().to_string();
+                continue;
+            }
+
+            let expected = match keyword {
+                "paddles" => 1,
+                "wall" | "bumper" => 4,
+                "portal" | "zone" => 5,
+                "mover" => 7,
+                _ => return Err(format!("Line {}: unknown object '{}'", i + 1, keyword)),
+            };
+
+            let values = Self::parse_values(args).map_err(|e| format!("Line {}: {}", i + 1, e))?;
+            if values.len() != expected {
+                return Err(format!("Line {}: '{}' takes {} values, got {}", i + 1, keyword, expected, values.len()));
+            }
+
+            let v = &values;
+            let point = |x: f32, y: f32| Vector2::new(x * SCREEN_SIZE.x, y * SCREEN_SIZE.y);
+            let rect = || Rectangle::new(v[0] * SCREEN_SIZE.x, v[1] * SCREEN_SIZE.y, v[2] * SCREEN_SIZE.x, v[3] * SCREEN_SIZE.y);
+
+            match keyword {
+                "paddles" => stage.paddle_padding = v[0] * SCREEN_SIZE.x,
+                "wall" => stage.objects.push(StageObject::Wall(rect())),
+                "mover" => {
+                    if v[6] <= 0.0 { return Err(format!("Line {}: movers need a positive time", i + 1)); }
+                    stage.objects.push(StageObject::Mover { rect: rect(), offset: point(v[4], v[5]), period: v[6] });
+                },
+                "bumper" => stage.objects.push(StageObject::Bumper { center: point(v[0], v[1]), radius: v[2] * SCREEN_SIZE.y, kick: v[3] }),
+                "portal" => stage.objects.push(StageObject::Portal { entry: point(v[0], v[1]), exit: point(v[2], v[3]), radius: v[4] * SCREEN_SIZE.y }),
+                _ => stage.objects.push(StageObject::Zone { rect: rect(), speed: v[4].max(0.0) }),
+            }
+        }
+
+        return Ok(stage);
+    }
+
+    fn parse_values(args: &str) -> Result<Vec<f32>, String> {
+        return args.split_whitespace()
+            .map(|word| word.parse::<f32>().map_err(|_| format!("'{}' isn't a number", word)))
+            .collect();
+    }
+}
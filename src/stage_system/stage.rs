@@ -0,0 +1,166 @@
+use std::f32::consts::PI;
+use std::fs;
+
+use super::*;
+use crate::utils::*;
+use crate::ui_system::Shape;
+use crate::collision_system::Collider;
+
+impl Stage {
+    pub fn get_built_in() -> Vec<Stage> {
+        return BUILT_IN_STAGES.iter().map(|text| Stage::parse(text).expect("Invalid built-in stage")).collect();
+    }
+
+    // Built-in stages, followed by the custom ones in the data dir. Broken files are skipped
+    pub fn load_all() -> Vec<Stage> {
+        let mut stages = Self::get_built_in();
+        let Ok(entries) = fs::read_dir(MiscUtils::get_data_dir().join(STAGES_DIR)) else { return stages; };
+
+        let mut paths: Vec<_> = entries.filter_map(|e| e.ok()).map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == STAGE_EXTENSION))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            match fs::read_to_string(&path).map_err(|e| e.to_string()).and_then(|text| Stage::parse(&text)) {
+                Ok(stage) => stages.push(stage),
+                Err(e) => println!("Couldn't load stage '{}': {}", path.display(), e),
+            }
+        }
+        return stages;
+    }
+
+    // What the ball bounces on at this time, with how hard each one kicks it
+    pub fn get_colliders(self: &Self, time: f32) -> Vec<(Collider, f32)> {
+        let mut colliders = Vec::new();
+
+        for object in &self.objects {
+            match *object {
+                StageObject::Wall(rect) => colliders.push((Collider::Rect(rect), 0.0)),
+                StageObject::Mover { rect, offset, period } => {
+                    colliders.push((Collider::Rect(Self::get_mover_rect(rect, offset, period, time)), 0.0));
+                },
+                StageObject::Bumper { center, radius, kick } => colliders.push((Collider::Circle(center, radius), kick)),
+                _ => {}
+            }
+        }
+        return colliders;
+    }
+
+    // Zones stack if they overlap
+    pub fn get_speed_scale(self: &Self, position: Vector2) -> f32 {
+        let mut scale = 1.0;
+        for object in &self.objects {
+            if let StageObject::Zone { rect, speed } = *object {
+                if rect.check_collision_point_rec(position) { scale *= speed; }
+            }
+        }
+        return scale;
+    }
+
+    // Where the ball comes out if its center is inside a portal
+    pub fn get_portal_exit(self: &Self, position: Vector2) -> Option<Vector2> {
+        for object in &self.objects {
+            if let StageObject::Portal { entry, exit, radius } = *object {
+                if position.distance_to(entry) < radius { return Some(exit); }
+                if position.distance_to(exit) < radius { return Some(entry); }
+            }
+        }
+        return None;
+    }
+
+    // Eases to the offset and back, so it slows down before turning
+    fn get_mover_rect(rect: Rectangle, offset: Vector2, period: f32, time: f32) -> Rectangle {
+        let progress = (1.0 - (time / period * 2.0 * PI).cos()) / 2.0;
+        return Rectangle::new(rect.x + offset.x * progress, rect.y + offset.y * progress, rect.width, rect.height);
+    }
+
+    // The stage drawn inside an area, so it can be shown smaller in menus
+    pub fn get_shapes(self: &Self, time: f32, area: Rectangle) -> Vec<Shape> {
+        let scale = area.width / SCREEN_SIZE.x;
+        let point = |p: Vector2| Vector2::new(area.x + p.x * scale, area.y + p.y * scale);
+        let rect = |r: Rectangle| Rectangle::new(area.x + r.x * scale, area.y + r.y * scale, r.width * scale, r.height * scale);
+        let mut shapes = Vec::new();
+
+        // Zones go first so everything else is drawn over them
+        for object in &self.objects {
+            if let StageObject::Zone { rect: zone, speed } = *object {
+                let color = if speed < 1.0 { Color::new(60, 90, 200, 60) } else { Color::new(230, 120, 30, 60) };
+                shapes.push(Shape::Rectangle(rect(zone), color));
+            }
+        }
+
+        for object in &self.objects {
+            match *object {
+                StageObject::Wall(wall) => shapes.push(Shape::Rectangle(rect(wall), Color::GRAY)),
+                StageObject::Mover { rect: mover, offset, period } => {
+                    shapes.push(Shape::Rectangle(rect(Self::get_mover_rect(mover, offset, period, time)), Color::LIGHTGRAY));
+                },
+                StageObject::Bumper { center, radius, .. } => {
+                    shapes.push(Shape::Circle(point(center), radius * scale, Color::new(200, 60, 160, 255)));
+                    shapes.push(Shape::CircleLines(point(center), radius * scale + 3.0 * scale, Color::PINK));
+                },
+                StageObject::Portal { entry, exit, radius } => {
+                    for end in [entry, exit] {
+                        shapes.push(Shape::Circle(point(end), radius * scale, Color::new(120, 40, 200, 120)));
+                        shapes.push(Shape::CircleLines(point(end), radius * scale, Color::PURPLE));
+                    }
+                },
+                StageObject::Zone { .. } => {},
+            }
+        }
+        return shapes;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn built_in_stages_load() {
+        let stages = Stage::get_built_in();
+        assert_eq!(stages.len(), BUILT_IN_STAGES.len());
+        assert_eq!(stages[0].name, "Classic");
+        assert!(stages[0].objects.is_empty());
+    }
+
+    #[test]
+    fn stage_files_use_screen_fractions() {
+        let stage = Stage::parse("name Test # comment\n\npaddles 0.1\nwall 0.5 0.5 0.25 0.25").unwrap();
+
+        assert_eq!(stage.name, "Test");
+        assert_eq!(stage.paddle_padding, SCREEN_SIZE.x * 0.1);
+        assert_eq!(stage.objects, vec![StageObject::Wall(Rectangle::new(320.0, 240.0, 160.0, 120.0))]);
+    }
+
+    #[test]
+    fn broken_lines_are_reported() {
+        assert_eq!(Stage::parse("name Test\nwal 0 0 1 1").err().unwrap(), "Line 2: unknown object 'wal'");
+        assert_eq!(Stage::parse("wall 0 0 1").err().unwrap(), "Line 1: 'wall' takes 4 values, got 3");
+        assert_eq!(Stage::parse("zone 0 0 1 1 fast").err().unwrap(), "Line 1: 'fast' isn't a number");
+    }
+
+    #[test]
+    fn portals_work_both_ways() {
+        let stage = Stage::parse("portal 0.25 0.5 0.75 0.5 0.05").unwrap();
+        let (entry, exit) = (Vector2::new(160.0, 240.0), Vector2::new(480.0, 240.0));
+
+        assert_eq!(stage.get_portal_exit(entry + Vector2::new(5.0, 0.0)), Some(exit));
+        assert_eq!(stage.get_portal_exit(exit), Some(entry));
+        assert_eq!(stage.get_portal_exit(Vector2::new(320.0, 240.0)), None);
+    }
+
+    #[test]
+    fn movers_go_to_their_offset_and_back() {
+        let stage = Stage::parse("mover 0 0 0.1 0.1 0.5 0 2").unwrap();
+        let get_x = |time: f32| match stage.get_colliders(time)[0].0 {
+            Collider::Rect(rect) => rect.x,
+            _ => panic!("Movers should be rects"),
+        };
+
+        assert_eq!(get_x(0.0), 0.0);
+        assert!((get_x(1.0) - 320.0).abs() < 1e-3);
+        assert!(get_x(2.0).abs() < 1e-3);
+    }
+}
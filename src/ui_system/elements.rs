@@ -1,5 +1,6 @@
 use super::*;
-use crate::utils::SCREEN_SIZE;
+use crate::utils::{SCREEN_SIZE, InputUtils};
+use raylib::consts::GamepadButton::*;
 
 impl Text {
     pub fn new(text: &str, relative_pos: Vector2, color: Color, size: i32,) -> Text {
@@ -31,7 +32,9 @@ impl Button {
                 SCREEN_SIZE.x * relative_pos.x as f32 - (measure_text(&text, 20) + 30) as f32 / 2.0,                 
                 SCREEN_SIZE.y * relative_pos.y - 10.0, measure_text(&text, 20) as f32 + 30.0, 
                 40.0
-            ), enabled
+            ), enabled,
+            is_selected: false,
+            nav_pressed: false,
         }
     }
 
@@ -49,19 +52,25 @@ impl Button {
 
     pub fn is_pressed(self: &Self, rl: &RaylibHandle) -> bool {
         if !self.enabled { return false; }
+        if self.nav_pressed { return true; }
         let clicked = rl.is_mouse_button_pressed(MouseButton::MOUSE_LEFT_BUTTON);
-        return self.is_focused(rl) && clicked;
+        return self.is_hovered(rl) && clicked;
     }
 
     pub fn is_focused(self: &Self, rl: &RaylibHandle) -> bool {
         if !self.enabled { return false; }
+        return self.is_selected || self.is_hovered(rl);
+    }
+
+    pub fn is_hovered(self: &Self, rl: &RaylibHandle) -> bool {
         let mouse_pos = rl.get_mouse_position();
         return self.rect.check_collision_point_rec(mouse_pos);
     }
 }
 
 impl TextField {
-    fn is_focused(self: &mut Self, rl: &RaylibHandle) -> bool {
+    pub fn is_focused(self: &mut Self, rl: &RaylibHandle) -> bool {
+        if self.is_selected { return true; }
         let mouse_pos = rl.get_mouse_position();
         return self.rects[1].check_collision_point_rec(mouse_pos);
     }
@@ -128,7 +137,92 @@ impl TextField {
             text: Text::new(placeholder, relative_pos, colors[0], text_size),
             placeholder: placeholder.to_string(),
             value: "".to_string(), max_length,
+            is_selected: false,
             colors, format
         }
     }
+}
+
+impl InitialsField {
+    const CHARSET: &'static str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+    pub fn get_initials(self: &Self) -> String {
+        return self.field.text.text.clone();
+    }
+
+    // TextField with an extra rect under the letter being edited
+    pub fn get_field(self: &Self) -> TextField {
+        let mut field = self.field.clone();
+        if self.is_confirmed { return field; }
+        
+        let text = &self.field.text;
+        let offset = measure_text(&text.text[..self.cursor], text.size) as f32;
+        let width = measure_text(&text.text[self.cursor..self.cursor + 1], text.size) as f32;
+        
+        field.rects.push(Rectangle::new(text.pos.x + offset, text.pos.y + text.size as f32, width, 2.0));
+        return field;
+    }
+
+    pub fn update(self: &mut Self, rl: &RaylibHandle) {
+        if self.is_confirmed { return; }
+
+        // Typing goes straight to the current letter
+        let key = unsafe { ffi::GetKeyPressed() };
+        let letter = char::from_u32(key as u32).unwrap_or('\0');
+        if key > 0 && self.field.format.is_match(&letter.to_string()) {
+            self.set_letter(letter);
+            self.move_cursor(1);
+            return;
+        }
+
+        let up = rl.is_key_pressed(KeyboardKey::KEY_UP) || InputUtils::is_any_gamepad_button_pressed(rl, GAMEPAD_BUTTON_LEFT_FACE_UP);
+        let down = rl.is_key_pressed(KeyboardKey::KEY_DOWN) || InputUtils::is_any_gamepad_button_pressed(rl, GAMEPAD_BUTTON_LEFT_FACE_DOWN);
+        let left = rl.is_key_pressed(KeyboardKey::KEY_LEFT) || rl.is_key_pressed(KeyboardKey::KEY_BACKSPACE) ||
+                   InputUtils::is_any_gamepad_button_pressed(rl, GAMEPAD_BUTTON_LEFT_FACE_LEFT) ||
+                   InputUtils::is_any_gamepad_button_pressed(rl, GAMEPAD_BUTTON_RIGHT_FACE_RIGHT);
+        let right = rl.is_key_pressed(KeyboardKey::KEY_RIGHT) || InputUtils::is_any_gamepad_button_pressed(rl, GAMEPAD_BUTTON_LEFT_FACE_RIGHT);
+        let confirm = rl.is_key_pressed(KeyboardKey::KEY_ENTER);
+        let next = InputUtils::is_any_gamepad_button_pressed(rl, GAMEPAD_BUTTON_RIGHT_FACE_DOWN);
+
+             if up { self.cycle_letter(1); }
+        else if down { self.cycle_letter(-1); }
+        else if left { self.move_cursor(-1); }
+        else if right { self.move_cursor(1); }
+        else if confirm { self.is_confirmed = true; }
+        
+        // Gamepad confirms after the last letter, like in the arcades
+        else if next {
+            if self.cursor == self.field.max_length - 1 { self.is_confirmed = true; }
+            else { self.move_cursor(1); }
+        }
+    }
+
+    fn set_letter(self: &mut Self, letter: char) {
+        let text = &mut self.field.text;
+        text.text.replace_range(self.cursor..self.cursor + 1, &letter.to_string());
+        text.centralize();
+    }
+
+    fn cycle_letter(self: &mut Self, step: i32) {
+        let current = self.field.text.text.chars().nth(self.cursor).unwrap();
+        let index = Self::CHARSET.find(current).unwrap_or(0) as i32;
+        let length = Self::CHARSET.len() as i32;
+
+        let new_index = (index + step).rem_euclid(length) as usize;
+        self.set_letter(Self::CHARSET.chars().nth(new_index).unwrap());
+    }
+
+    fn move_cursor(self: &mut Self, step: i32) {
+        let new_cursor = self.cursor as i32 + step;
+        self.cursor = new_cursor.clamp(0, self.field.max_length as i32 - 1) as usize;
+    }
+
+    pub fn new(relative_pos: Vector2) -> InitialsField {
+        InitialsField {
+            field: TextField::new(Regex::new("[A-Z0-9]").expect("Invalid regex"), "AAA", 80.0, 20, relative_pos, 5.0,
+                                  vec![Color::WHITE, Color::new(30, 30, 30, 255), Color::GOLD], 3),
+            cursor: 0,
+            is_confirmed: false,
+        }
+    }
 }
\ No newline at end of file
@@ -1,15 +1,28 @@
 mod elements;
+mod navigation;
 
 use raylib::prelude::*;
 use regex::Regex;
 
 use crate::GameScene;
+use crate::input_system::InputDevice;
 
 #[derive(Clone)]
 pub struct ScreenElements {
     pub texts: Vec<Text>,
     pub fields: Vec<TextField>,
     pub buttons: Vec<(Button, Color)>,
+    pub focused: Vec<usize>, // Buttons selected with keyboard or gamepad
+    pub shapes: Vec<Shape>,  // Drawn below everything else
+}
+
+#[derive(Clone)]
+pub enum Shape {
+    Circle(Vector2, f32, Color),
+    CircleLines(Vector2, f32, Color),
+    Rectangle(Rectangle, Color),
+    RectangleLines(Rectangle, Color),
+    Line(Vector2, Vector2, Color),
 }
 
 impl ScreenElements {
@@ -20,17 +33,63 @@ impl ScreenElements {
     pub fn new(rl: &RaylibHandle, texts: Vec<Text>, buttons: Vec<Button>, fields: Vec<TextField>) -> ScreenElements {
         let length = buttons.len();
         let mut _buttons = Vec::with_capacity(length);
+        let mut focused = Vec::new();
         
         for i in 0..length {
             _buttons.push((buttons[i].clone(), buttons[i].get_color(rl)));
+            if buttons[i].is_selected { focused.push(i); }
         }
 
         return ScreenElements{
             texts: texts, 
             fields: fields, 
-            buttons: _buttons
+            buttons: _buttons,
+            focused: focused,
+            shapes: Vec::new(),
         };
     }
+
+    pub fn draw(self: &Self, draw_handle: &mut impl RaylibDraw) {
+        for shape in &self.shapes { shape.draw(draw_handle); }
+
+        for text in &self.texts {
+            draw_handle.draw_text(&text.text, text.pos.x as i32, text.pos.y as i32, 
+                text.size, text.color);
+        }
+
+        for (button, color) in &self.buttons {
+            draw_handle.draw_text(&button.text, button.pos.x as i32, button.pos.y as i32,
+                20 as i32, color);
+        }
+
+        for i in &self.focused {
+            let (button, color) = &self.buttons[*i];
+            draw_handle.draw_rectangle_lines_ex(button.rect, 1, color);
+        }
+
+        for field in &self.fields {
+            for (rect, color) in field.rects.iter().zip(&field.colors) {
+                draw_handle.draw_rectangle_rec(rect, color);
+            }
+            if field.is_selected {
+                draw_handle.draw_rectangle_lines_ex(field.rects[0], 2, Self::FOCUSED_COLOR);
+            }
+            draw_handle.draw_text(&field.text.text, field.text.pos.x as i32, field.text.pos.y as i32, 
+                field.text.size, field.text.color);
+        }
+    }
+}
+
+impl Shape {
+    pub fn draw(self: &Self, draw_handle: &mut impl RaylibDraw) {
+        match *self {
+            Shape::Circle(center, radius, color) => draw_handle.draw_circle_v(center, radius, color),
+            Shape::CircleLines(center, radius, color) => draw_handle.draw_circle_lines(center.x as i32, center.y as i32, radius, color),
+            Shape::Rectangle(rect, color) => draw_handle.draw_rectangle_rec(rect, color),
+            Shape::RectangleLines(rect, color) => draw_handle.draw_rectangle_lines_ex(rect, 1, color),
+            Shape::Line(start, end, color) => draw_handle.draw_line_v(start, end, color),
+        }
+    }
 }
 
 pub trait UIScreen {
@@ -39,9 +98,28 @@ pub trait UIScreen {
 
     fn get_next_screen(&self, rl: &RaylibHandle) -> Box<dyn UIScreen>;
     fn get_next_scene(&self, rl: &RaylibHandle) -> Box<dyn GameScene>;
+    fn get_previous_screen(&self) -> Option<Box<dyn UIScreen>>;
 
     fn update(self: &mut Self, rl: &RaylibHandle);
     fn get_elements(self: &mut Self, rl: &RaylibHandle) -> ScreenElements;
+    fn get_focus_order(self: &mut Self) -> Vec<Vec<&mut dyn Focusable>>;
+}
+
+// Anything that can be selected with keyboard or gamepad
+pub trait Focusable {
+    fn can_focus(self: &Self) -> bool;
+    fn set_focus(self: &mut Self, is_selected: bool, is_pressed: bool);
+    fn is_editing(self: &mut Self, _rl: &RaylibHandle) -> bool { return false; } // Keys are typed into it
+    fn is_clicked(self: &Self, _rl: &RaylibHandle) -> bool { return false; }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum NavCommand { None, Up, Down, Left, Right, Confirm, Back }
+
+pub struct FocusNavigator {
+    pub index: Option<usize>,
+    select_first: bool,
+    last_mouse_pos: Option<Vector2>,
 }
 
 
@@ -60,6 +138,9 @@ pub struct Button {
     pub enabled: bool,
     pub text: String,
     pub pos: Vector2,
+
+    pub is_selected: bool,
+    pub nav_pressed: bool,
 }
 
 #[derive(Clone)]
@@ -74,4 +155,13 @@ pub struct TextField {
     pub text: Text,
     pub value: String,
     pub placeholder: String,
+    pub is_selected: bool,
+}
+
+// Arcade style name entry, each letter is picked with up/down 
+#[derive(Clone)]
+pub struct InitialsField {
+    pub field: TextField,
+    pub cursor: usize,
+    pub is_confirmed: bool,
 }
\ No newline at end of file
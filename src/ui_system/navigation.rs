@@ -0,0 +1,172 @@
+use super::*;
+use crate::utils::InputUtils;
+use crate::input_system::{DeviceId, InputAction};
+use crate::audio_system::{AudioPlayer, SoundEffect};
+use raylib::consts::KeyboardKey::*;
+use raylib::consts::GamepadButton::*;
+
+impl NavCommand {
+    // Confirm and Back follow the devices' bindings, the usual keys are only used if none of them binds it
+    pub fn read(rl: &RaylibHandle, devices: &mut [&mut dyn InputDevice], is_editing: bool) -> NavCommand {
+        let pressed = |keys: &[KeyboardKey], buttons: &[GamepadButton]| {
+            keys.iter().any(|k| rl.is_key_pressed(*k)) || 
+            buttons.iter().any(|b| InputUtils::is_any_gamepad_button_pressed(rl, *b))
+        };
+
+        if pressed(&[KEY_UP, KEY_W], &[GAMEPAD_BUTTON_LEFT_FACE_UP]) { return NavCommand::Up; }
+        if pressed(&[KEY_DOWN, KEY_S], &[GAMEPAD_BUTTON_LEFT_FACE_DOWN]) { return NavCommand::Down; }
+        if pressed(&[KEY_LEFT, KEY_A], &[GAMEPAD_BUTTON_LEFT_FACE_LEFT]) { return NavCommand::Left; }
+        if pressed(&[KEY_RIGHT, KEY_D], &[GAMEPAD_BUTTON_LEFT_FACE_RIGHT]) { return NavCommand::Right; }
+
+        let confirm = Self::is_action_pressed(devices, InputAction::Confirm, is_editing);
+        if confirm.unwrap_or_else(|| pressed(&[KEY_ENTER, KEY_SPACE], &[GAMEPAD_BUTTON_RIGHT_FACE_DOWN])) { return NavCommand::Confirm; }
+        let back = Self::is_action_pressed(devices, InputAction::Back, is_editing);
+        if back.unwrap_or_else(|| pressed(&[KEY_ESCAPE], &[GAMEPAD_BUTTON_RIGHT_FACE_RIGHT])) { return NavCommand::Back; }
+        return NavCommand::None;
+    }
+
+    // None if no device binds the action. Keyboards are left out while typing into a field, and
+    // the mouse always is, since it presses whatever it points at
+    pub fn is_action_pressed(devices: &mut [&mut dyn InputDevice], action: InputAction, is_editing: bool) -> Option<bool> {
+        let mut bound: Vec<&mut &mut dyn InputDevice> = devices.iter_mut().filter(|d| {
+            let is_typing = is_editing && matches!(d.get_id(), DeviceId::Keyboard(_));
+            return !is_typing && d.get_id() != DeviceId::Mouse && !d.get_profile().get_bindings(action).is_empty();
+        }).collect();
+
+        if bound.is_empty() { return None; }
+        return Some(bound.iter_mut().any(|d| d.is_action_pressed(action)));
+    }
+}
+
+impl Focusable for Button {
+    fn can_focus(self: &Self) -> bool { return self.enabled; }
+    fn set_focus(self: &mut Self, is_selected: bool, is_pressed: bool) {
+        self.is_selected = is_selected;
+        self.nav_pressed = is_pressed;
+    }
+    fn is_clicked(self: &Self, rl: &RaylibHandle) -> bool {
+        return self.enabled && self.is_hovered(rl) && rl.is_mouse_button_pressed(MouseButton::MOUSE_LEFT_BUTTON);
+    }
+}
+
+impl Focusable for TextField {
+    fn can_focus(self: &Self) -> bool { return true; }
+    fn set_focus(self: &mut Self, is_selected: bool, _is_pressed: bool) {
+        self.is_selected = is_selected;
+    }
+    fn is_editing(self: &mut Self, rl: &RaylibHandle) -> bool { return self.is_focused(rl); }
+}
+
+impl FocusNavigator {
+    // Rows are walked with up/down. Rows with more than one element are 
+    // selectors ('<' and '>'), where left/right presses the first/last element.
+    // Confirm and Back are read from the given devices' bindings
+    pub fn update(self: &mut Self, rl: &RaylibHandle, mut rows: Vec<Vec<&mut dyn Focusable>>,
+                  devices: &mut [&mut dyn InputDevice]) -> NavCommand {
+        let is_editing = rows.iter_mut().flatten().any(|e| e.is_editing(rl));
+        let command = NavCommand::read(rl, devices, is_editing);
+
+        // Mouse takes over the focus when it moves
+        let mouse_pos = rl.get_mouse_position();
+        if self.last_mouse_pos.is_some_and(|p| p != mouse_pos) { 
+            self.index = None; 
+            self.select_first = false;
+        }
+        self.last_mouse_pos = Some(mouse_pos);
+
+        let focusable: Vec<usize> = (0..rows.len()).filter(|i| rows[*i].iter().any(|e| e.can_focus())).collect();
+        if self.select_first && !focusable.is_empty() {
+            self.index = Some(focusable[0]);
+            self.select_first = false;
+        }
+        let is_selector = self.index.is_some_and(|i| rows[i].len() > 1);
+
+        let step = match command {
+            NavCommand::Up => -1,
+            NavCommand::Down => 1,
+            NavCommand::Left if !is_selector => -1,
+            NavCommand::Right if !is_selector => 1,
+            _ => 0
+        };
+
+        if step != 0 && !focusable.is_empty() {
+            self.index = match self.index.and_then(|i| focusable.iter().position(|f| *f == i)) {
+                Some(pos) => Some(focusable[(pos as i32 + step).rem_euclid(focusable.len() as i32) as usize]),
+                None => Some(focusable[0])
+            };
+            AudioPlayer::play(SoundEffect::MenuMove);
+        }
+
+        let mut is_confirmed = rows.iter().flatten().any(|e| e.is_clicked(rl));
+        for (i, row) in rows.iter_mut().enumerate() {
+            let last = row.len().saturating_sub(1);
+            for (j, element) in row.iter_mut().enumerate() {
+                let is_selected = self.index == Some(i) && element.can_focus();
+                let is_pressed = is_selected && match command {
+                    NavCommand::Confirm => last == 0,
+                    NavCommand::Left => last > 0 && j == 0,
+                    NavCommand::Right => last > 0 && j == last,
+                    _ => false
+                };
+                element.set_focus(is_selected, is_pressed);
+                is_confirmed |= is_pressed;
+            }
+        }
+
+        if is_confirmed { AudioPlayer::play(SoundEffect::MenuConfirm); }
+
+        return command;
+    }
+
+    // Keyboard and gamepad users start the next screen with something already selected
+    pub fn reset(self: &mut Self) {
+        self.select_first = self.index.is_some();
+        self.index = None;
+    }
+
+    pub fn row<T: Focusable>(elements: &mut [T]) -> Vec<&mut dyn Focusable> {
+        return elements.iter_mut().map(|e| e as &mut dyn Focusable).collect();
+    }
+
+    pub fn new() -> FocusNavigator {
+        return FocusNavigator { index: None, select_first: false, last_mouse_pos: None };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input_system::{KeyboardInput, MouseInput, ScriptedInput, ScriptedFrame};
+
+    fn pressing(actions: &[InputAction]) -> ScriptedInput {
+        let mut input = ScriptedInput::new(false, vec![(0.0, ScriptedFrame::new(actions, Vector2::zero()))]);
+        input.poll(0.0);
+        return input;
+    }
+
+    #[test]
+    fn commands_follow_the_bindings() {
+        let (mut idle, mut confirming) = (pressing(&[]), pressing(&[InputAction::Confirm]));
+        let mut devices: Vec<&mut dyn InputDevice> = vec![&mut idle, &mut confirming];
+        assert_eq!(NavCommand::is_action_pressed(&mut devices, InputAction::Confirm, false), Some(true));
+        assert_eq!(NavCommand::is_action_pressed(&mut devices, InputAction::Back, false), Some(false));
+
+        // Unbound actions are left to the default keys
+        let mut profile = devices[1].get_profile();
+        profile.bindings.retain(|(a, _)| *a != InputAction::Confirm);
+        for device in devices.iter_mut() { device.set_profile(profile.clone()); }
+        assert_eq!(NavCommand::is_action_pressed(&mut devices, InputAction::Confirm, false), None);
+    }
+
+    #[test]
+    fn keyboards_are_ignored_while_typing() {
+        let mut keyboard = KeyboardInput::new(false);
+        assert_eq!(NavCommand::is_action_pressed(&mut [&mut keyboard], InputAction::Back, true), None);
+    }
+
+    #[test]
+    fn mouse_is_never_read_for_commands() {
+        let mut mouse = MouseInput::new();
+        assert_eq!(NavCommand::is_action_pressed(&mut [&mut mouse], InputAction::Back, false), None);
+    }
+}
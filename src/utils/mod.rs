@@ -3,44 +3,29 @@ use std::any::TypeId;
 use std::fs;
 use std::env;
 use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 use bincode::deserialize;
 use bincode::serialize;
 use raylib::prelude::*;
 use raylib::prelude::Vector2;
 
-use crate::input_system::*;
 use crate::networking::*;
+use crate::settings_system::{Settings, VideoSettings};
 
 pub struct MiscUtils; 
 pub struct DebugUtils;
 pub struct InputUtils;
 pub struct NetworkUtils;
 
+// Everything is laid out at this size, then scaled to fit the window
 pub const SCREEN_SIZE: Vector2 = Vector2 { x: 640.0, y: 480.0 };
-const MAX_CONNECTED_GAMEPADS: usize = 4;
+pub const MAX_CONNECTED_GAMEPADS: usize = 4;
 
 
 impl InputUtils {
-    pub fn get_device_by_id(id: i32) -> Box<dyn InputDevice>{
-        match id {
-            0 => return Box::new(KeyboardInput::new(true)),
-            1 => return Box::new(KeyboardInput::new(false)),
-            _ => return Box::new(GamepadInput::new(id - 2, true))
-        }
-    }
-    
-    pub fn get_connected_devices(rl: &RaylibHandle) -> Vec<Box<dyn InputDevice>> {
-        let mut devices: Vec<Box<dyn InputDevice>> = Vec::with_capacity(MAX_CONNECTED_GAMEPADS + 2);
-        devices.insert(0, Box::new(KeyboardInput::new(true)));
-        devices.insert(1, Box::new(KeyboardInput::new(false)));
-    
-        for i in 0..MAX_CONNECTED_GAMEPADS{
-            if rl.is_gamepad_available(i as i32) {
-                devices.insert(i + 2, Box::new(GamepadInput::new(i as i32, true)));
-            }
-        }
-    
-        return devices;
+    pub fn is_any_gamepad_button_pressed(rl: &RaylibHandle, button: GamepadButton) -> bool {
+        return (0..MAX_CONNECTED_GAMEPADS as i32).any(|i| rl.is_gamepad_available(i) && rl.is_gamepad_button_pressed(i, button));
     }
 }
 
@@ -51,32 +36,71 @@ impl NetworkUtils {
 }
 
 impl MiscUtils {
-    pub fn init_window() -> (RaylibHandle, RaylibThread) {
-        let (mut rl_handle, _thread) = raylib::init()
-            .size(SCREEN_SIZE.x as i32, SCREEN_SIZE.y as i32)
-            .title("Pong 2").vsync().build();
-    
-        rl_handle.set_target_fps(60);
+    pub fn init_window(settings: &VideoSettings) -> (RaylibHandle, RaylibThread) {
+        let mut builder = raylib::init();
+        builder.size(settings.resolution.0, settings.resolution.1).title("Pong 2").resizable();
+        if settings.vsync { builder.vsync(); }
+
+        let (mut rl_handle, _thread) = builder.build();
+        rl_handle.set_window_min_size(SCREEN_SIZE.x as i32 / 2, SCREEN_SIZE.y as i32 / 2);
+        rl_handle.set_exit_key(None); // ESC is used for pausing
         return (rl_handle, _thread);
     }
     
-    pub fn get_highscore() -> i32 {
-        match fs::read_to_string("highscore.txt") {
-            Ok(s) => return s.parse::<i32>().unwrap(),
-            // Create file if doesn't exist
-            _ => { 
-                println!("File 'highscore.txt' doesn't exist, creating...");
-                let mut file = fs::File::create("highscore.txt").unwrap();
-                file.write_all(b"0").unwrap();
-                return 0;
-             }
+    pub fn get_data_dir() -> PathBuf {
+        // Fallback to the working directory if there's no XDG data dir
+        let dir = match dirs::data_dir() {
+            Some(d) => d.join("pong_2"),
+            None => PathBuf::from(".")
+        };
+
+        if let Err(e) = fs::create_dir_all(&dir) {
+            println!("Couldn't create data directory '{}': {}", dir.display(), e);
         }
+        return dir;
     }
-    
-    pub fn save_highscore(i: i32) {
-        let mut file = fs::OpenOptions::new().write(true).open("highscore.txt").unwrap();
-        let buffer: String = i.to_string();
-        file.write_all(buffer.as_bytes()).unwrap();
+
+    pub fn get_config_dir() -> PathBuf {
+        // Fallback to the working directory if there's no XDG config dir
+        let dir = match dirs::config_dir() {
+            Some(d) => d.join("pong_2"),
+            None => PathBuf::from(".")
+        };
+
+        if let Err(e) = fs::create_dir_all(&dir) {
+            println!("Couldn't create config directory '{}': {}", dir.display(), e);
+        }
+        return dir;
+    }
+
+    pub fn get_timestamp() -> u64 {
+        return SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    }
+
+    // Formats a UNIX timestamp as YYYY-MM-DD (https://howardhinnant.github.io/date_algorithms.html#civil_from_days)
+    pub fn format_date(timestamp: u64) -> String {
+        let days = (timestamp / 86400) as i64 + 719468;
+        let era = days.div_euclid(146097);
+        let day_of_era = days.rem_euclid(146097);
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+
+        let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+        let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        
+        return format!("{:04}-{:02}-{:02}", year, month, day);
+    }
+
+    // Write to a temporary file first, so a crash mid-write never leaves a half written file behind
+    pub fn write_atomically(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+        let tmp_path = path.with_extension("tmp");
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+
+        return fs::rename(&tmp_path, path);
     }
 }
 
@@ -133,7 +157,7 @@ impl DebugUtils {
 
         
         let remote = env::var("REMOTE").expect("REMOTE variable not set");
-        let mut net = NetworkManager::new(remote.to_string());
+        let mut net = NetworkManager::new(remote.to_string(), Settings::load().network.port);
         print!("{}[2J", 27 as char);
 
         while (true) {